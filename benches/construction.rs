@@ -0,0 +1,151 @@
+//! Time to construct and propagate errors.
+//!
+//! `baseline` is the same error defined with the last release of
+//! error-chain, 0.12.4, and a plain enum is used as a reference point. To
+//! compare against another revision, run
+//! `cargo bench -- --save-baseline before` on it, then
+//! `cargo bench -- --baseline before` on this one.
+//!
+//! Backtraces are suppressed unless stated otherwise, as they dominate the
+//! cost of an error. `suppress_backtraces` does not apply to `baseline`, so
+//! compare it with `RUST_BACKTRACE` unset.
+
+#[macro_use]
+extern crate criterion;
+#[macro_use]
+extern crate error_chain;
+extern crate error_chain_baseline;
+
+use criterion::{black_box, Criterion};
+
+mod plain {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    pub enum Error {
+        Unexpected(char, usize),
+    }
+
+    pub type Result<T> = ::std::result::Result<T, Error>;
+
+    pub fn parse(c: char, at: usize) -> Result<()> {
+        Err(Error::Unexpected(c, at))
+    }
+}
+
+mod baseline {
+    error_chain_baseline::error_chain! {
+        errors {
+            Unexpected(c: char, at: usize)
+        }
+    }
+
+    pub fn parse(c: char, at: usize) -> Result<()> {
+        Err(ErrorKind::Unexpected(c, at))?
+    }
+}
+
+mod regular {
+    error_chain! {
+        errors {
+            Unexpected(c: char, at: usize)
+        }
+    }
+
+    pub fn parse(c: char, at: usize) -> Result<()> {
+        Err(ErrorKind::Unexpected(c, at))?
+    }
+}
+
+mod thin {
+    error_chain! {
+        errors {
+            Unexpected(c: char, at: usize)
+        }
+
+        thin
+    }
+
+    pub fn parse(c: char, at: usize) -> Result<()> {
+        Err(ErrorKind::Unexpected(c, at))?
+    }
+}
+
+macro_rules! propagate {
+    ($module:ident) => {{
+        #[inline(never)]
+        fn one(c: char) -> $module::Result<()> {
+            $module::parse(black_box(c), 3)?;
+            Ok(())
+        }
+        #[inline(never)]
+        fn two(c: char) -> $module::Result<()> {
+            one(c)?;
+            Ok(())
+        }
+        #[inline(never)]
+        fn three(c: char) -> $module::Result<()> {
+            two(c)?;
+            Ok(())
+        }
+        three
+    }};
+}
+
+fn construct(c: &mut Criterion) {
+    let _guard = error_chain::suppress_backtraces();
+    let mut group = c.benchmark_group("construct");
+    group.bench_function("plain", |b| {
+        b.iter(|| plain::parse(black_box('x'), black_box(3)))
+    });
+    group.bench_function("baseline", |b| {
+        b.iter(|| baseline::parse(black_box('x'), black_box(3)))
+    });
+    group.bench_function("regular", |b| {
+        b.iter(|| regular::parse(black_box('x'), black_box(3)))
+    });
+    group.bench_function("thin", |b| {
+        b.iter(|| thin::parse(black_box('x'), black_box(3)))
+    });
+    group.finish();
+}
+
+fn propagate(c: &mut Criterion) {
+    let _guard = error_chain::suppress_backtraces();
+    let mut group = c.benchmark_group("propagate");
+    let plain = propagate!(plain);
+    group.bench_function("plain", |b| b.iter(|| plain(black_box('x'))));
+    let baseline = propagate!(baseline);
+    group.bench_function("baseline", |b| b.iter(|| baseline(black_box('x'))));
+    let regular = propagate!(regular);
+    group.bench_function("regular", |b| b.iter(|| regular(black_box('x'))));
+    let thin = propagate!(thin);
+    group.bench_function("thin", |b| b.iter(|| thin(black_box('x'))));
+    group.finish();
+}
+
+fn chain(c: &mut Criterion) {
+    // Both `ResultExt` traits apply to any `Result`, so they are named.
+    let _guard = error_chain::suppress_backtraces();
+    let mut group = c.benchmark_group("chain_err");
+    group.bench_function("baseline", |b| {
+        b.iter(|| {
+            baseline::ResultExt::chain_err(baseline::parse(black_box('x'), 3), || "while parsing")
+        })
+    });
+    group.bench_function("regular", |b| {
+        b.iter(|| {
+            regular::ResultExt::chain_err(regular::parse(black_box('x'), 3), || "while parsing")
+        })
+    });
+    group.finish();
+}
+
+fn backtrace(c: &mut Criterion) {
+    // Only meaningful when run with `RUST_BACKTRACE=1`.
+    c.bench_function("construct_with_backtrace", |b| {
+        b.iter(|| regular::parse(black_box('x'), black_box(3)))
+    });
+}
+
+criterion_group!(benches, construct, propagate, chain, backtrace);
+criterion_main!(benches);
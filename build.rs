@@ -4,6 +4,10 @@ use std::env;
 use version_check::is_min_version;
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(has_error_source)");
+    println!("cargo:rustc-check-cfg=cfg(has_error_description_deprecated)");
+    println!("cargo:rustc-check-cfg=cfg(build, values(\"debug\", \"release\"))");
+
     // Switch on for versions that have Error::source
     // As introduced by https://github.com/rust-lang/rust/pull/53533
     if is_min_version("1.30").unwrap_or(false) {
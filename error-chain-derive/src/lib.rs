@@ -0,0 +1,463 @@
+//! `#[derive(ErrorChain)]`, generating the types of [`error_chain!`] from an
+//! enum written by hand, which rustfmt and IDEs understand.
+//!
+//! It is re-exported by `error-chain` with its `derive` feature:
+//!
+//! ```
+//! #[macro_use]
+//! extern crate error_chain;
+//!
+//! mod other {
+//!     error_chain! {}
+//! }
+//!
+//! #[derive(Debug, ErrorChain)]
+//! pub enum ErrorKind {
+//!     /// A convenient variant for String.
+//!     #[error_chain(msg)]
+//!     Msg(String),
+//!
+//!     #[error_chain(link = other::Error)]
+//!     Other(other::ErrorKind),
+//!
+//!     #[error_chain(foreign)]
+//!     Io(::std::io::Error),
+//!
+//!     /// The toolchain name is not valid.
+//!     #[error_chain(description = "invalid toolchain name")]
+//!     #[error_chain(display = "invalid toolchain name: '{}'")]
+//!     InvalidToolchainName(String),
+//!
+//!     #[error_chain(display = "{name} {version:?} is not installed")]
+//!     NotInstalled { name: String, version: Option<String> },
+//! }
+//!
+//! fn install() -> Result<()> {
+//!     bail!(ErrorKind::InvalidToolchainName("nightly?".into()))
+//! }
+//!
+//! # fn main() {
+//! let e = install().chain_err(|| "cannot install").unwrap_err();
+//! assert_eq!(e.to_string(), "cannot install");
+//! assert_eq!(
+//!     e.iter().nth(1).unwrap().to_string(),
+//!     "invalid toolchain name: 'nightly?'"
+//! );
+//! # }
+//! ```
+//!
+//! The enum is used as the `ErrorKind`, and the `Error` struct, the
+//! `ResultExt` trait, the `Result` alias and the `From` conversions are
+//! generated as they are by `error_chain!`, as well as the methods of the
+//! kind such as `description` and `variant_name`. The generated types have
+//! the visibility of the enum, which must derive `Debug` itself.
+//!
+//! The variants take these attributes:
+//!
+//! - `#[error_chain(link = path::to::Error)]` on a variant holding the kind
+//!   of another `error_chain!` type, as in the `links` section.
+//! - `#[error_chain(foreign)]` on a variant holding any other error, as in
+//!   the `foreign_links` section.
+//! - `#[error_chain(msg)]` on a `Msg(String)` variant, adding the
+//!   conversions from strings used by `bail!("...")`.
+//! - `#[error_chain(display = "...")]` and
+//!   `#[error_chain(description = "...")]` on the other variants. The
+//!   format string refers to the fields by name, or by position for tuple
+//!   variants, and `{}` takes the fields in order.
+//!
+//! The names of the generated types can be changed on the enum with
+//! `#[error_chain(error = MyError, result_ext = MyResultExt, result =
+//! MyResult)]`, and `#[error_chain(thin)]` and `#[error_chain(into_io)]`
+//! have the effect of the `thin` and `into_io` annotations.
+//!
+//! The generated code refers to the `error_chain` crate as `::error_chain`;
+//! if it is renamed or reexported, give its path with
+//! `#[error_chain(crate = path::to::error_chain)]`.
+//!
+//! The derive does not support the other features of `error_chain!` yet:
+//! the `io_kind`, `http_status`, `message_id`, `code`, `sensitive`,
+//! `severity`, `retryable` and `help` clauses, the `opaque_links` section,
+//! the `msg_variant` annotation, `#[boxed]`, and the mapping functions and
+//! `retryable` classifiers of `foreign_links`. The variants behave as if
+//! they had none of these clauses; use `error_chain!` when one of them is
+//! needed.
+//!
+//! [`error_chain!`]: https://docs.rs/error-chain/*/error_chain/macro.error_chain.html
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, LitStr, Path, Type, Variant};
+
+/// Generates the `error_chain!` types for an `ErrorKind` enum, see the
+/// [crate documentation](index.html).
+#[proc_macro_derive(ErrorChain, attributes(error_chain))]
+pub fn derive_error_chain(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(compile_error).into()
+}
+
+/// Reports `error` with a bare `compile_error!`: syn's own expansion names
+/// `::core`, which does not resolve in 2015 edition crates.
+fn compile_error(error: syn::Error) -> TokenStream2 {
+    error
+        .into_iter()
+        .map(|error| {
+            let message = error.to_string();
+            quote_spanned!(error.span()=> compile_error! { #message })
+        })
+        .collect()
+}
+
+/// The `#[error_chain(..)]` attributes of the enum.
+struct Types {
+    error: Ident,
+    result_ext: Ident,
+    result: Ident,
+    thin: bool,
+    into_io: bool,
+    krate: Path,
+}
+
+/// The `#[error_chain(..)]` attributes of a variant.
+#[derive(Default)]
+struct VariantAttrs {
+    link: Option<Path>,
+    foreign: bool,
+    msg: bool,
+    display: Option<LitStr>,
+    description: Option<LitStr>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match input.data {
+        Data::Enum(ref data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`#[derive(ErrorChain)]` only supports enums",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "`#[derive(ErrorChain)]` does not support generic enums",
+        ));
+    }
+
+    let types = parse_types(&input.attrs)?;
+    let mut msg = false;
+    let mut links = Vec::new();
+    let mut foreign_links = Vec::new();
+    let mut errors = Vec::new();
+
+    for variant in &data.variants {
+        let attrs = parse_variant_attrs(variant)?;
+        let name = &variant.ident;
+        let cfgs = variant.attrs.iter().filter(|a| a.path().is_ident("cfg"));
+        if attrs.msg {
+            check_msg(variant)?;
+            msg = true;
+        } else if let Some(ref link) = attrs.link {
+            let kind = single_field(variant, "link")?;
+            links.push(quote! { #name(#link, #kind) #(#cfgs)*; });
+        } else if attrs.foreign {
+            let error = single_field(variant, "foreign")?;
+            foreign_links.push(quote! { #name(#error) [] [] #(#cfgs)*; });
+        } else {
+            errors.push(error_variant(variant, &attrs)?);
+        }
+    }
+
+    let vis = &input.vis;
+    let kind = &input.ident;
+    let error = &types.error;
+    let result_ext = &types.result_ext;
+    let result = &types.result;
+    let krate = &types.krate;
+    let thin = if types.thin { quote!(thin) } else { quote!() };
+    let into_io = if types.into_io {
+        quote!(into_io)
+    } else {
+        quote!()
+    };
+    let skip_msg_variant = if msg {
+        quote!()
+    } else {
+        quote!(skip_msg_variant)
+    };
+
+    Ok(quote! {
+        #krate::impl_error_chain_processed! {
+            types {
+                [#vis] #error, #kind, #result_ext, #result;
+            }
+            repr { #thin }
+            io { #into_io }
+            kind { DECLARED }
+            opaque_links {}
+            #skip_msg_variant
+            links { #(#links)* }
+            foreign_links { #(#foreign_links)* }
+            errors { #(#errors)* }
+        }
+    })
+}
+
+fn parse_types(attrs: &[Attribute]) -> syn::Result<Types> {
+    let mut types = Types {
+        error: Ident::new("Error", Span::call_site()),
+        result_ext: Ident::new("ResultExt", Span::call_site()),
+        result: Ident::new("Result", Span::call_site()),
+        thin: false,
+        into_io: false,
+        krate: syn::parse_quote!(::error_chain),
+    };
+    for attr in attrs.iter().filter(|a| a.path().is_ident("error_chain")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("error") {
+                types.error = meta.value()?.parse()?;
+            } else if meta.path.is_ident("result_ext") {
+                types.result_ext = meta.value()?.parse()?;
+            } else if meta.path.is_ident("result") {
+                types.result = meta.value()?.parse()?;
+            } else if meta.path.is_ident("thin") {
+                types.thin = true;
+            } else if meta.path.is_ident("into_io") {
+                types.into_io = true;
+            } else if meta.path.is_ident("crate") {
+                types.krate = meta.value()?.parse()?;
+            } else {
+                return Err(meta.error(
+                    "unknown `error_chain` attribute, expected `error`, `result_ext`, `result`, `thin`, `into_io` or `crate`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(types)
+}
+
+fn parse_variant_attrs(variant: &Variant) -> syn::Result<VariantAttrs> {
+    let mut attrs = VariantAttrs::default();
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("error_chain"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("link") {
+                attrs.link = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("foreign") {
+                attrs.foreign = true;
+            } else if meta.path.is_ident("msg") {
+                attrs.msg = true;
+            } else if meta.path.is_ident("display") {
+                attrs.display = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("description") {
+                attrs.description = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error(
+                    "unknown `error_chain` attribute, expected `link`, `foreign`, `msg`, `display` or `description`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+
+    let kinds = [attrs.link.is_some(), attrs.foreign, attrs.msg];
+    if kinds.iter().filter(|&&k| k).count() > 1 {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            "only one of `link`, `foreign` and `msg` can be used on a variant",
+        ));
+    }
+    if (attrs.display.is_some() || attrs.description.is_some()) && kinds.iter().any(|&k| k) {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            "`display` and `description` can't be used on `link`, `foreign` or `msg` variants",
+        ));
+    }
+    Ok(attrs)
+}
+
+/// Returns the type of the only field of a tuple variant.
+fn single_field<'a>(variant: &'a Variant, attr: &str) -> syn::Result<&'a Type> {
+    match variant.fields {
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => Ok(&fields.unnamed[0].ty),
+        _ => Err(syn::Error::new_spanned(
+            &variant.fields,
+            format!("a `{}` variant must have a single unnamed field", attr),
+        )),
+    }
+}
+
+fn check_msg(variant: &Variant) -> syn::Result<()> {
+    if variant.ident != "Msg" {
+        return Err(syn::Error::new_spanned(
+            &variant.ident,
+            "the `msg` variant must be named `Msg`",
+        ));
+    }
+    single_field(variant, "msg").map(|_| ())
+}
+
+/// Renders a variant in the syntax of the `errors` section.
+fn error_variant(variant: &Variant, attrs: &VariantAttrs) -> syn::Result<TokenStream2> {
+    let name = &variant.ident;
+    let metas = variant
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc") || a.path().is_ident("cfg"));
+
+    let (fields, names): (TokenStream2, Vec<Ident>) = match variant.fields {
+        Fields::Unit => (quote!(), Vec::new()),
+        Fields::Unnamed(ref fields) => {
+            let names: Vec<_> = (0..fields.unnamed.len())
+                .map(|i| Ident::new(&format!("_{}", i), Span::call_site()))
+                .collect();
+            let types = fields.unnamed.iter().map(|f| &f.ty);
+            let names_ = names.iter();
+            (quote!((#(#names_: #types),*)), names)
+        }
+        Fields::Named(ref fields) => {
+            let names: Vec<_> = fields
+                .named
+                .iter()
+                .map(|f| f.ident.clone().unwrap())
+                .collect();
+            let types = fields.named.iter().map(|f| &f.ty);
+            let names_ = names.iter();
+            (quote!({ #(#names_: #types),* }), names)
+        }
+    };
+
+    let description = attrs.description.as_ref().map(|d| quote!(description(#d)));
+    let display = match attrs.display {
+        Some(ref display) => {
+            let (format, args) = rewrite_format(display, &names)?;
+            quote!(display(#format #(, #args = #args)*))
+        }
+        None => quote!(),
+    };
+
+    Ok(quote! {
+        #(#metas)*
+        #name #fields {
+            #description
+            #display
+        }
+    })
+}
+
+/// Rewrites the `display` format string so that every field it uses is a
+/// named argument: `{0}` becomes `{_0}`, and `{}` takes the next field.
+/// Width and precision arguments, as in `{:width$}` or `{:.1$}`, are
+/// rewritten the same way; `.*` is rejected.
+fn rewrite_format(format: &LitStr, names: &[Ident]) -> syn::Result<(LitStr, Vec<Ident>)> {
+    let value = format.value();
+    let mut out = String::with_capacity(value.len());
+    let mut used: Vec<Ident> = Vec::new();
+    let mut next = 0;
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        out.push(c);
+        if c == '}' {
+            if chars.peek() == Some(&'}') {
+                out.push(chars.next().unwrap());
+            }
+            continue;
+        }
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            out.push(chars.next().unwrap());
+            continue;
+        }
+
+        let mut arg = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '}' || c == ':' {
+                break;
+            }
+            arg.push(c);
+            chars.next();
+        }
+        let arg = arg.trim();
+        let field = if arg.is_empty() {
+            next += 1;
+            names.get(next - 1)
+        } else {
+            find_field(arg, names)
+        };
+        let field = field_or_error(field, arg, format)?;
+        out.push_str(&field.to_string());
+        if !used.contains(field) {
+            used.push(field.clone());
+        }
+
+        // The spec may name a width or a precision argument as `arg$`.
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '}' {
+                break;
+            }
+            chars.next();
+            if c.is_alphanumeric() || c == '_' {
+                word.push(c);
+                continue;
+            }
+            if c == '$' && !word.is_empty() {
+                let field = field_or_error(find_field(&word, names), &word, format)?;
+                out.push_str(&field.to_string());
+                if !used.contains(field) {
+                    used.push(field.clone());
+                }
+            } else {
+                if c == '*' && out.ends_with('.') {
+                    return Err(syn::Error::new_spanned(
+                        format,
+                        "`.*` is not supported, name the precision with `.field$`",
+                    ));
+                }
+                out.push_str(&word);
+            }
+            out.push(c);
+            word.clear();
+        }
+        out.push_str(&word);
+    }
+
+    Ok((LitStr::new(&out, format.span()), used))
+}
+
+/// Finds the field a format argument refers to, by position or by name.
+fn find_field<'a>(arg: &str, names: &'a [Ident]) -> Option<&'a Ident> {
+    match arg.parse::<usize>() {
+        Ok(i) => names.get(i),
+        Err(_) => names.iter().find(|name| *name == arg),
+    }
+}
+
+fn field_or_error<'a>(
+    field: Option<&'a Ident>,
+    arg: &str,
+    format: &LitStr,
+) -> syn::Result<&'a Ident> {
+    field.ok_or_else(|| {
+        let message = if arg.is_empty() {
+            "the format string uses more fields than the variant has".to_string()
+        } else {
+            format!("the variant has no field `{}`", arg)
+        };
+        syn::Error::new_spanned(format, message)
+    })
+}
@@ -0,0 +1,58 @@
+//! Prints the catalog of the errors below, as Markdown or, with `--json`,
+//! as JSON.
+//!
+//! `cargo run --example catalog -- --json > errors.json`
+
+#[macro_use]
+extern crate error_chain;
+
+use error_chain::catalog::Catalog;
+use std::env;
+
+mod config {
+    error_chain! {
+        errors {
+            /// The configuration file could not be found in any of the
+            /// searched directories.
+            NotFound(path: String) {
+                description("configuration not found")
+                display("no configuration at '{}'", path)
+                code("CFG001")
+            }
+
+            /// A key of the configuration file has an invalid value.
+            InvalidValue(key: String) {
+                description("invalid configuration value")
+                display("invalid value for '{}'", key)
+                code("CFG002")
+            }
+        }
+    }
+}
+
+error_chain! {
+    links {
+        Config(config::Error, config::ErrorKind);
+    }
+
+    errors {
+        /// The remote server refused the connection.
+        ConnectionRefused(host: String) {
+            description("connection refused")
+            display("connection to {} refused", host)
+            code("NET001")
+        }
+    }
+}
+
+fn main() {
+    let catalog = Catalog::new()
+        .register("app", ErrorKind::catalog())
+        .register("app::config", config::ErrorKind::catalog());
+
+    if env::args().any(|arg| arg == "--json") {
+        println!("{}", catalog.to_json());
+    } else {
+        print!("{}", catalog.to_markdown());
+    }
+}
@@ -1,6 +1,9 @@
 //! Exits with exit code 0 if backtraces are disabled and 1 if they are enabled.
 //! Used by tests to make sure backtraces are available when they should be. Should not be used
 //! outside of the tests.
+//!
+//! With the `suppressed` argument, the error is created while backtraces are
+//! suppressed on the current thread.
 
 #[macro_use]
 extern crate error_chain;
@@ -12,6 +15,10 @@ error_chain! {
 }
 
 fn main() {
+    let _guard = match ::std::env::args().nth(1) {
+        Some(ref arg) if arg == "suppressed" => Some(error_chain::suppress_backtraces()),
+        _ => None,
+    };
     let err = Error::from(ErrorKind::MyError);
     let has_backtrace = err.backtrace().is_some();
     ::std::process::exit(has_backtrace as i32);
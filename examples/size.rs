@@ -1,6 +1,8 @@
 #[macro_use]
 extern crate error_chain;
 
+use std::error;
+use std::fmt;
 use std::mem::{size_of, size_of_val};
 
 error_chain! {
@@ -10,6 +12,49 @@ error_chain! {
     }
 }
 
+mod thin {
+    error_chain! {
+        errors {
+            AVariant
+            Another
+        }
+
+        thin
+    }
+}
+
+/// A foreign error much larger than the other variants, e.g. a parser error
+/// with its spans.
+#[derive(Debug)]
+pub struct ParseError {
+    spans: [usize; 32],
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "parse error at {}", self.spans[0])
+    }
+}
+
+impl error::Error for ParseError {}
+
+mod inline {
+    error_chain! {
+        foreign_links {
+            Parse(super::ParseError);
+        }
+    }
+}
+
+mod boxed {
+    error_chain! {
+        foreign_links {
+            #[boxed]
+            Parse(super::ParseError);
+        }
+    }
+}
+
 fn main() {
     println!("Memory usage in bytes");
     println!("---------------------");
@@ -24,7 +69,30 @@ fn main() {
     let state = error_chain::State {
         next_error: None,
         backtrace: error_chain::InternalBacktrace::new(),
+        help: None,
     };
     println!("      State.next_error: {}", size_of_val(&state.next_error));
     println!("      State.backtrace: {}", size_of_val(&state.backtrace));
+    println!("      State.help: {}", size_of_val(&state.help));
+    println!();
+    println!("With `thin`");
+    println!("-----------");
+    println!("Result<()>: {}", size_of::<thin::Result<()>>());
+    println!("  Error: {}", size_of::<thin::Error>());
+    println!(
+        "    Box<(ErrorKind, State)>: {} (on the heap)",
+        size_of::<(thin::ErrorKind, error_chain::State)>()
+    );
+    println!();
+    println!("With a large `foreign_links` error");
+    println!("----------------------------------");
+    println!("Result<()>: {}", size_of::<inline::Result<()>>());
+    println!("  ErrorKind: {}", size_of::<inline::ErrorKind>());
+    println!("    ParseError: {}", size_of::<ParseError>());
+    println!();
+    println!("With `#[boxed]`");
+    println!("---------------");
+    println!("Result<()>: {}", size_of::<boxed::Result<()>>());
+    println!("  ErrorKind: {}", size_of::<boxed::ErrorKind>());
+    println!("    Box<ParseError>: {}", size_of::<Box<ParseError>>());
 }
@@ -0,0 +1,152 @@
+//! Conversions between error_chain errors and `anyhow::Error`, behind the
+//! `anyhow` feature.
+//!
+//! Errors generated by `error_chain!` are not `Sync`, as their causes only
+//! need to be `Send`, so the blanket `From` impl of `anyhow::Error` does not
+//! apply and `?` alone does not compile:
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate error_chain;
+//! # extern crate anyhow;
+//! error_chain! {}
+//!
+//! fn run() -> anyhow::Result<()> {
+//!     Err(Error::from("bad digit"))?;
+//!     Ok(())
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Nor can `error_chain!` generate a `From<Error> for anyhow::Error` impl:
+//! it would conflict with that blanket impl, since the compiler does not
+//! rule out that the error becomes `Sync` in a future release. The
+//! [`IntoAnyhow`] extension trait converts them instead, keeping the message
+//! of every error of the chain and the backtrace:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! # extern crate anyhow;
+//! use error_chain::anyhow::{Chained, IntoAnyhow};
+//!
+//! error_chain! {}
+//!
+//! fn parse() -> Result<()> {
+//!     Err(Error::from("bad digit").chain_err(|| "invalid header"))
+//! }
+//!
+//! fn run() -> anyhow::Result<()> {
+//!     parse().into_anyhow()?;
+//!     Ok(())
+//! }
+//!
+//! # fn main() {
+//! let e = run().unwrap_err();
+//! let messages: Vec<_> = e.chain().map(|e| e.to_string()).collect();
+//! assert_eq!(messages, ["invalid header", "bad digit"]);
+//! // The original error, and its backtrace, can be recovered.
+//! let original = e.downcast::<Chained>().unwrap().into_inner();
+//! assert!(original.downcast_ref::<Error>().is_some());
+//! # }
+//! ```
+//!
+//! The other way around, [`Anyhow`] wraps an `anyhow::Error` so that it can
+//! be used in `foreign_links` or as the cause given to `chain_err`, with its
+//! own chain still visible through `iter`.
+//!
+//! [`IntoAnyhow`]: trait.IntoAnyhow.html
+//! [`Anyhow`]: struct.Anyhow.html
+
+use std::error;
+use std::fmt;
+
+use anyhow_crate;
+
+use ChainedError;
+
+pub use Chained;
+
+/// Converts error_chain errors, or the error of a `Result`, into
+/// `anyhow::Error`.
+pub trait IntoAnyhow {
+    /// The converted type.
+    type Output;
+
+    /// Performs the conversion.
+    fn into_anyhow(self) -> Self::Output;
+}
+
+impl<E: ChainedError> IntoAnyhow for E {
+    type Output = anyhow_crate::Error;
+
+    fn into_anyhow(self) -> anyhow_crate::Error {
+        anyhow_crate::Error::new(Chained::new(self))
+    }
+}
+
+impl<T, E: ChainedError> IntoAnyhow for Result<T, E> {
+    type Output = anyhow_crate::Result<T>;
+
+    fn into_anyhow(self) -> anyhow_crate::Result<T> {
+        self.map_err(IntoAnyhow::into_anyhow)
+    }
+}
+
+/// Wraps an `anyhow::Error` so that it implements `std::error::Error`.
+///
+/// It displays the outermost message of the `anyhow::Error`, and its
+/// `source` is the next error of the `anyhow` chain.
+///
+/// ```
+/// # #[macro_use] extern crate error_chain;
+/// # extern crate anyhow;
+/// use error_chain::anyhow::Anyhow;
+///
+/// error_chain! {
+///     foreign_links {
+///         Dynamic(Anyhow);
+///     }
+/// }
+///
+/// fn load() -> anyhow::Result<()> {
+///     Err(anyhow::anyhow!("disk full")).map_err(|e| e.context("cannot save"))
+/// }
+///
+/// # fn main() {
+/// let e = load().map_err(Anyhow::from).chain_err(|| "autosave failed").unwrap_err();
+/// let messages: Vec<_> = e.iter().map(|e| e.to_string()).collect();
+/// assert_eq!(messages, ["autosave failed", "cannot save", "disk full"]);
+/// # }
+/// ```
+pub struct Anyhow(pub anyhow_crate::Error);
+
+impl Anyhow {
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> anyhow_crate::Error {
+        self.0
+    }
+}
+
+impl From<anyhow_crate::Error> for Anyhow {
+    fn from(e: anyhow_crate::Error) -> Anyhow {
+        Anyhow(e)
+    }
+}
+
+impl fmt::Debug for Anyhow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Anyhow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+impl error::Error for Anyhow {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        self.0.source()
+    }
+}
@@ -1,5 +1,62 @@
 pub use self::imp::{Backtrace, InternalBacktrace};
 
+#[cfg(feature = "std")]
+use std::cell::Cell;
+#[cfg(feature = "std")]
+use std::marker::PhantomData;
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// Number of live `SuppressBacktraces` guards on this thread.
+    static SUPPRESSED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Disables backtrace capture on the current thread until the returned guard
+/// is dropped.
+///
+/// This is meant for tests comparing the output of `display_chain`, which
+/// otherwise depends on whether `RUST_BACKTRACE` is set. Guards can be
+/// nested.
+///
+/// ```
+/// # #[macro_use] extern crate error_chain;
+/// error_chain! {}
+///
+/// # fn main() {
+/// let _guard = error_chain::suppress_backtraces();
+/// assert!(Error::from("no backtrace").backtrace().is_none());
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn suppress_backtraces() -> SuppressBacktraces {
+    SUPPRESSED.with(|n| n.set(n.get() + 1));
+    SuppressBacktraces {
+        _not_send: PhantomData,
+    }
+}
+
+/// Guard returned by [`suppress_backtraces`](fn.suppress_backtraces.html).
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct SuppressBacktraces {
+    // The guard counts for the thread it was created on.
+    _not_send: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl Drop for SuppressBacktraces {
+    fn drop(&mut self) {
+        SUPPRESSED.with(|n| n.set(n.get() - 1));
+    }
+}
+
+/// Returns `true` if a `SuppressBacktraces` guard is alive on this thread.
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "backtrace"), allow(dead_code))]
+fn suppressed() -> bool {
+    SUPPRESSED.with(|n| n.get() > 0)
+}
+
 #[cfg(feature = "backtrace")]
 mod imp {
     extern crate backtrace;
@@ -27,27 +84,42 @@ mod imp {
 
     pub use self::backtrace::Backtrace;
 
+    /// Whether `RUST_BACKTRACE` enables backtraces: 0 if the environment has
+    /// not been read yet, 1 if disabled, 2 if enabled.
+    static ENABLED: AtomicUsize = AtomicUsize::new(0);
+
     impl InternalBacktrace {
         /// Returns a backtrace of the current call stack if `RUST_BACKTRACE`
         /// is set to anything but ``0``, and `None` otherwise.  This is used
         /// in the generated error implementations.
+        ///
+        /// Once the environment has been read, this is a single atomic load
+        /// when backtraces are disabled, and does not allocate.
         #[doc(hidden)]
+        #[inline]
         pub fn new() -> InternalBacktrace {
-            static ENABLED: AtomicUsize = AtomicUsize::new(0);
-
-            match ENABLED.load(Ordering::SeqCst) {
-                0 => {
-                    let enabled = match env::var_os("RUST_BACKTRACE") {
-                        Some(ref val) if val != "0" => true,
-                        _ => false,
-                    };
-                    ENABLED.store(enabled as usize + 1, Ordering::SeqCst);
-                    if !enabled {
-                        return InternalBacktrace { backtrace: None };
-                    }
+            match ENABLED.load(Ordering::Relaxed) {
+                1 => InternalBacktrace { backtrace: None },
+                _ => InternalBacktrace::capture(),
+            }
+        }
+
+        #[cold]
+        #[inline(never)]
+        fn capture() -> InternalBacktrace {
+            if ENABLED.load(Ordering::Relaxed) == 0 {
+                let enabled = match env::var_os("RUST_BACKTRACE") {
+                    Some(ref val) => val != "0",
+                    None => false,
+                };
+                ENABLED.store(enabled as usize + 1, Ordering::Relaxed);
+                if !enabled {
+                    return InternalBacktrace { backtrace: None };
                 }
-                1 => return InternalBacktrace { backtrace: None },
-                _ => {}
+            }
+
+            if super::suppressed() {
+                return InternalBacktrace { backtrace: None };
             }
 
             InternalBacktrace {
@@ -98,6 +170,7 @@ mod imp {
     impl InternalBacktrace {
         /// Returns a new backtrace
         #[doc(hidden)]
+        #[inline]
         pub fn new() -> InternalBacktrace {
             InternalBacktrace {}
         }
@@ -0,0 +1,233 @@
+//! Listing every variant of an `ErrorKind`, to publish the errors a program
+//! can produce.
+//!
+//! `ErrorKind::catalog()` returns an [`Entry`] per variant, with its name,
+//! its doc comment, its description when it is a string literal and the
+//! code given by its `code` clause. [`Catalog`] renders the catalogs of
+//! several error types as Markdown or JSON, for instance from a small
+//! binary or a `build.rs` script (see `examples/catalog.rs`):
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::catalog::Catalog;
+//!
+//! error_chain! {
+//!     errors {
+//!         /// The configuration file does not exist.
+//!         ConfigNotFound(path: String) {
+//!             description("configuration not found")
+//!             display("no configuration at {}", path)
+//!             code("E0001")
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! // The first entry is the `Msg` variant.
+//! let entry = &ErrorKind::catalog()[1];
+//! assert_eq!(entry.name(), "ConfigNotFound");
+//! assert_eq!(entry.doc(), "The configuration file does not exist.");
+//! assert_eq!(entry.description(), Some("configuration not found"));
+//! assert_eq!(entry.code(), Some("E0001"));
+//! assert_eq!(ErrorKind::ConfigNotFound("a".into()).code(), Some("E0001"));
+//!
+//! let catalog = Catalog::new().register("app", ErrorKind::catalog());
+//! assert!(catalog.to_markdown().contains("### `ConfigNotFound`"));
+//! assert!(catalog.to_json().contains(r#"{"name":"ConfigNotFound","code":"E0001","#));
+//! # }
+//! ```
+//!
+//! [`Entry`]: struct.Entry.html
+//! [`Catalog`]: struct.Catalog.html
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::fmt::Write;
+
+use http::push_json_string;
+
+/// A variant of an `ErrorKind`, as listed by `ErrorKind::catalog()`.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    name: &'static str,
+    attributes: &'static [&'static str],
+    description: Option<&'static str>,
+    code: Option<&'static str>,
+}
+
+impl Entry {
+    /// Creates an entry, `attributes` being the stringified attributes of
+    /// the variant.
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        attributes: &'static [&'static str],
+        description: Option<&'static str>,
+        code: Option<&'static str>,
+    ) -> Entry {
+        Entry {
+            name,
+            attributes,
+            description,
+            code,
+        }
+    }
+
+    /// The name of the variant, e.g. `"Msg"`.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The doc comment of the variant, or an empty string.
+    pub fn doc(&self) -> String {
+        let mut doc = String::new();
+        for line in self.attributes.iter().filter_map(|a| doc_attribute(a)) {
+            if !doc.is_empty() {
+                doc.push('\n');
+            }
+            doc.push_str(line.strip_prefix(' ').unwrap_or(&line));
+        }
+        String::from(doc.trim())
+    }
+
+    /// The description of the variant, if given by a string literal.
+    pub fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+
+    /// The code given by the `code` clause of the variant.
+    pub fn code(&self) -> Option<&'static str> {
+        self.code
+    }
+}
+
+/// Renders the catalogs of several error types.
+#[derive(Debug, Default)]
+pub struct Catalog {
+    catalogs: Vec<(&'static str, &'static [Entry])>,
+}
+
+impl Catalog {
+    /// Creates an empty catalog.
+    pub fn new() -> Catalog {
+        Catalog::default()
+    }
+
+    /// Adds the entries of an error type, under the heading `name`.
+    pub fn register(mut self, name: &'static str, entries: &'static [Entry]) -> Catalog {
+        self.catalogs.push((name, entries));
+        self
+    }
+
+    /// Renders the catalogs as Markdown, with a section per error type and
+    /// a subsection per variant.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        for &(name, entries) in &self.catalogs {
+            if !md.is_empty() {
+                md.push('\n');
+            }
+            writeln!(md, "## {}", name).unwrap();
+            for entry in entries {
+                writeln!(md, "\n### `{}`", entry.name).unwrap();
+                if entry.code.is_some() || entry.description.is_some() {
+                    md.push('\n');
+                }
+                if let Some(code) = entry.code {
+                    writeln!(md, "- Code: `{}`", code).unwrap();
+                }
+                if let Some(description) = entry.description {
+                    writeln!(md, "- Description: {}", description).unwrap();
+                }
+                let doc = entry.doc();
+                if !doc.is_empty() {
+                    writeln!(md, "\n{}", doc).unwrap();
+                }
+            }
+        }
+        md
+    }
+
+    /// Renders the catalogs as a JSON array of `{"name", "entries"}`
+    /// objects, each entry having a `name`, `code`, `description` and `doc`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, &(name, entries)) in self.catalogs.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"name\":");
+            push_json_string(&mut json, name);
+            json.push_str(",\"entries\":[");
+            for (j, entry) in entries.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+                json.push_str("{\"name\":");
+                push_json_string(&mut json, entry.name);
+                json.push_str(",\"code\":");
+                push_optional_json_string(&mut json, entry.code);
+                json.push_str(",\"description\":");
+                push_optional_json_string(&mut json, entry.description);
+                json.push_str(",\"doc\":");
+                push_json_string(&mut json, &entry.doc());
+                json.push('}');
+            }
+            json.push_str("]}");
+        }
+        json.push(']');
+        json
+    }
+}
+
+fn push_optional_json_string(json: &mut String, s: Option<&str>) {
+    match s {
+        Some(s) => push_json_string(json, s),
+        None => json.push_str("null"),
+    }
+}
+
+/// Returns the text of a stringified `doc = "..."` attribute.
+fn doc_attribute(attribute: &str) -> Option<String> {
+    let value = attribute.strip_prefix("doc")?.trim_start();
+    let value = value.strip_prefix('=')?.trim_start();
+    if let Some(raw) = value.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = raw[hashes..].strip_prefix('"')?;
+        let end = raw.len().checked_sub(hashes + 1)?;
+        return Some(String::from(&raw[..end]));
+    }
+    unescape(value.strip_prefix('"')?.strip_suffix('"')?)
+}
+
+/// Unescapes the content of a string literal.
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            'r' => out.push('\r'),
+            't' => out.push('\t'),
+            '0' => out.push('\0'),
+            'x' => {
+                let hex: String = chars.by_ref().take(2).collect();
+                out.push(u8::from_str_radix(&hex, 16).ok()? as char);
+            }
+            'u' => {
+                let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                out.push(::std::char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            '\n' => {
+                let rest = chars.as_str().trim_start();
+                chars = rest.chars();
+            }
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
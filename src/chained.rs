@@ -0,0 +1,116 @@
+//! A `Sync` snapshot of an error chain.
+
+use std::error;
+use std::fmt;
+use std::sync::Mutex;
+
+use {Backtrace, ChainedError, InternalBacktrace};
+
+/// An error of an `error_chain!` type, converted into a `Send + Sync` error.
+///
+/// The errors generated by `error_chain!` are only `Send`, while
+/// `std::io::Error` and `anyhow::Error` need their causes to be `Sync` as
+/// well. This keeps a snapshot of the messages of the chain, and the
+/// original error, which can be recovered with
+/// [`into_inner`](#method.into_inner).
+pub struct Chained {
+    message: String,
+    source: Option<Box<Link>>,
+    backtrace: InternalBacktrace,
+    #[allow(unknown_lints, bare_trait_objects)]
+    error: Mutex<Box<error::Error + Send>>,
+}
+
+impl Chained {
+    /// Converts an error, taking a snapshot of the messages of its chain.
+    pub fn new<E: ChainedError>(e: E) -> Chained {
+        let mut messages: Vec<String> = e.iter().map(|e| e.to_string()).collect();
+        let mut source = None;
+        while messages.len() > 1 {
+            source = Some(Box::new(Link {
+                message: messages.pop().unwrap(),
+                source,
+            }));
+        }
+        Chained {
+            message: messages.pop().unwrap(),
+            source,
+            backtrace: e.state().backtrace.clone(),
+            error: Mutex::new(Box::new(e)),
+        }
+    }
+
+    /// Returns `true` if the original error is an `E`.
+    pub fn is<E: error::Error + 'static>(&self) -> bool {
+        match self.error.lock() {
+            Ok(e) => e.is::<E>(),
+            Err(poisoned) => poisoned.get_ref().is::<E>(),
+        }
+    }
+
+    /// Returns the backtrace of the original error, if any.
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_backtrace()
+    }
+
+    /// Returns the original error.
+    #[allow(unknown_lints, bare_trait_objects)]
+    pub fn into_inner(self) -> Box<error::Error + Send> {
+        match self.error.into_inner() {
+            Ok(e) => e,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl fmt::Debug for Chained {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Error: {}", self.message)?;
+        let mut source = self.source.as_ref();
+        while let Some(link) = source {
+            writeln!(f, "Caused by: {}", link.message)?;
+            source = link.source.as_ref();
+        }
+        if let Some(backtrace) = self.backtrace() {
+            writeln!(f, "{:?}", backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Chained {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+impl error::Error for Chained {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|e| &**e as &(error::Error + 'static))
+    }
+}
+
+/// The message of a cause of a `Chained` error.
+#[derive(Debug)]
+struct Link {
+    message: String,
+    source: Option<Box<Link>>,
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+impl error::Error for Link {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|e| &**e as &(error::Error + 'static))
+    }
+}
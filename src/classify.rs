@@ -0,0 +1,152 @@
+//! Classifying errors by severity and by whether they are worth retrying.
+//!
+//! The `severity` clause of a variant gives the [`Severity`] returned by the
+//! `severity` method of its kind, `Error` by default. The `retryable` clause
+//! marks the variant as transient for `is_retryable`, either always or
+//! depending on its fields with `retryable(expr)`. `links` and
+//! `opaque_links` variants use the classification of the linked kind.
+//!
+//! A `foreign_links` entry is classified by the function, or closure, of its
+//! `retryable` clause, taking a reference to the foreign error. Without the
+//! clause, `std::io::Error`s are retryable according to [`is_transient_io`]
+//! and other foreign errors are not retryable:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use std::io;
+//!
+//! fn is_busy(e: &io::Error) -> bool {
+//!     e.kind() == io::ErrorKind::AddrInUse
+//! }
+//!
+//! error_chain! {
+//!     foreign_links {
+//!         Io(io::Error) retryable(is_busy);
+//!     }
+//! }
+//!
+//! # fn main() {
+//! assert!(ErrorKind::Io(io::ErrorKind::AddrInUse.into()).is_retryable());
+//! assert!(!ErrorKind::Io(io::ErrorKind::TimedOut.into()).is_retryable());
+//! # }
+//! ```
+//!
+//! [`ChainedError::any_retryable`] looks for a retryable error in the whole
+//! chain, whether or not the causes are of a type from `links`:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::classify::Severity;
+//! use error_chain::ChainedError;
+//!
+//! error_chain! {
+//!     errors {
+//!         Unavailable(attempts: u32) {
+//!             display("service unavailable after {} attempts", attempts)
+//!             retryable(*attempts < 3)
+//!         }
+//!         Corrupted {
+//!             display("corrupted database")
+//!             severity(Fatal)
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! assert!(ErrorKind::Unavailable(1).is_retryable());
+//! assert!(!ErrorKind::Unavailable(3).is_retryable());
+//! assert_eq!(ErrorKind::Unavailable(1).severity(), Severity::Error);
+//! assert_eq!(ErrorKind::Corrupted.severity(), Severity::Fatal);
+//!
+//! let e = Error::from(ErrorKind::Unavailable(1)).chain_err(|| "cannot sync");
+//! assert!(!e.kind().is_retryable());
+//! assert!(e.any_retryable());
+//! # }
+//! ```
+//!
+//! [`Severity`]: enum.Severity.html
+//! [`is_transient_io`]: fn.is_transient_io.html
+//! [`ChainedError::any_retryable`]: ../trait.ChainedError.html#method.any_retryable
+
+use std::any::Any;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use erased;
+use ChainedError;
+use ErrorRef;
+
+/// How serious an error is, given by the `severity` clause of a variant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The operation failed, but the program can carry on as usual.
+    Warning,
+    /// The operation failed. This is the default.
+    #[default]
+    Error,
+    /// The program, or the component which failed, cannot carry on.
+    Fatal,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        })
+    }
+}
+
+/// The default classifier of `std::io::Error`s: `Interrupted`,
+/// `WouldBlock`, `TimedOut`, `ConnectionReset` and `ConnectionAborted`
+/// errors are retryable.
+///
+/// It is used for the `foreign_links` entries without a `retryable` clause,
+/// and for the `std::io::Error`s chained as causes.
+#[cfg(feature = "std")]
+pub fn is_transient_io(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+    )
+}
+
+/// Whether a foreign error is retryable, only known for `std::io::Error`.
+#[doc(hidden)]
+#[allow(unknown_lints, bare_trait_objects)]
+pub fn foreign_is_retryable<E: Any>(e: &E) -> bool {
+    #[cfg(feature = "std")]
+    {
+        if let Some(e) = (e as &Any).downcast_ref::<io::Error>() {
+            return is_transient_io(e);
+        }
+    }
+    let _ = e;
+    false
+}
+
+/// Returns `true` if an error of the chain of `e` is retryable.
+pub(crate) fn any_retryable<T: ChainedError>(e: &T) -> bool {
+    e.iter().any(is_retryable::<T>)
+}
+
+/// Whether `e` is a retryable error generated by `error_chain!`, or a
+/// retryable `std::io::Error`.
+fn is_retryable<T: ChainedError>(e: ErrorRef) -> bool {
+    if let Some(e) = erased::extract::<T>(e) {
+        return e.is_retryable();
+    }
+    #[cfg(feature = "std")]
+    {
+        if let Some(e) = e.downcast_ref::<io::Error>() {
+            return is_transient_io(e);
+        }
+    }
+    false
+}
@@ -0,0 +1,183 @@
+use alloc::vec::Vec;
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::iter::FromIterator;
+
+use ChainedError;
+
+/// Extension trait for collecting an iterator of `Result`s into a single
+/// error_chain error.
+///
+/// Unlike collecting into `Result<C, E>`, which stops at the first failure,
+/// this keeps going and gathers every failure into a [`Failures`] value. If
+/// anything failed, the returned error has a caller-supplied kind and the
+/// `Failures` as its cause.
+///
+/// ```
+/// # #[macro_use] extern crate error_chain;
+/// use error_chain::CollectErrors;
+///
+/// error_chain! {}
+///
+/// # fn main() {
+/// let inputs = vec!["1", "x", "3", "y"];
+/// let res: Result<Vec<i32>> = inputs
+///     .iter()
+///     .map(|s| s.parse::<i32>())
+///     .collect_errors(|f| format!("{} of {} items failed", f.len(), f.total()));
+///
+/// let e = res.unwrap_err();
+/// assert_eq!(e.to_string(), "2 of 4 items failed");
+/// # }
+/// ```
+///
+/// [`Failures`]: struct.Failures.html
+pub trait CollectErrors<T, E>: Iterator<Item = Result<T, E>> + Sized
+where
+    E: error::Error + Send + 'static,
+{
+    /// Collects every `Ok` value into `C`, or returns an error carrying
+    /// every failure.
+    ///
+    /// `summary` is called once, if anything failed, to build the kind of
+    /// the returned error.
+    #[track_caller]
+    fn collect_errors<C, CE, F, K>(self, summary: F) -> Result<C, CE>
+    where
+        C: FromIterator<T>,
+        CE: ChainedError,
+        F: FnOnce(&Failures<E>) -> K,
+        K: Into<CE::ErrorKind>,
+    {
+        self.collect_errors_max(usize::MAX, summary)
+    }
+
+    /// Like `collect_errors`, but stops consuming the iterator once
+    /// `max_failures` failures have been seen. `0` is treated as `1`, stopping
+    /// at the first failure.
+    #[track_caller]
+    fn collect_errors_max<C, CE, F, K>(self, max_failures: usize, summary: F) -> Result<C, CE>
+    where
+        C: FromIterator<T>,
+        CE: ChainedError,
+        F: FnOnce(&Failures<E>) -> K,
+        K: Into<CE::ErrorKind>,
+    {
+        let mut failures = Failures {
+            errors: Vec::new(),
+            total: 0,
+        };
+        let collected = Shunt {
+            iter: self,
+            failures: &mut failures,
+            max_failures: cmp::max(max_failures, 1),
+        }
+        .collect();
+
+        if failures.is_empty() {
+            Ok(collected)
+        } else {
+            let kind = summary(&failures);
+            Err(CE::with_chain(failures, kind))
+        }
+    }
+}
+
+impl<I, T, E> CollectErrors<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: error::Error + Send + 'static,
+{
+}
+
+/// Yields the `Ok` values of an iterator, setting the errors aside.
+struct Shunt<'a, I, E: 'a> {
+    iter: I,
+    failures: &'a mut Failures<E>,
+    max_failures: usize,
+}
+
+impl<'a, I, T, E> Iterator for Shunt<'a, I, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.failures.len() < self.max_failures {
+            let index = self.failures.total;
+            match self.iter.next() {
+                Some(Ok(value)) => {
+                    self.failures.total += 1;
+                    return Some(value);
+                }
+                Some(Err(e)) => {
+                    self.failures.total += 1;
+                    self.failures.errors.push((index, e));
+                }
+                None => return None,
+            }
+        }
+        None
+    }
+}
+
+/// Every failure gathered by [`CollectErrors`], used as the cause of the
+/// error it returns.
+///
+/// [`CollectErrors`]: trait.CollectErrors.html
+#[derive(Debug)]
+pub struct Failures<E> {
+    errors: Vec<(usize, E)>,
+    total: usize,
+}
+
+impl<E> Failures<E> {
+    /// The failures, each with the index of the item that produced it.
+    pub fn errors(&self) -> &[(usize, E)] {
+        &self.errors
+    }
+
+    /// Consumes `self`, returning the failures with their indices.
+    pub fn into_errors(self) -> Vec<(usize, E)> {
+        self.errors
+    }
+
+    /// The number of failures.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if nothing failed.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The number of items consumed from the iterator, successful or not.
+    ///
+    /// When collection stopped early because of a failure limit, this
+    /// does not include the items that were never reached.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Failures<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &(index, ref e)) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "item {}: {}", index, e)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: error::Error> error::Error for Failures<E> {
+    #[cfg(not(has_error_description_deprecated))]
+    fn description(&self) -> &str {
+        "multiple errors"
+    }
+}
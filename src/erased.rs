@@ -0,0 +1,78 @@
+//! Recognizing the errors generated by `error_chain!` in a chain of
+//! `dyn Error`s.
+//!
+//! Each error type registers its `extract_erased` the first time one of its
+//! values is created, so the causes of a chain are recognized whatever
+//! their type, and not only the error types from `links`.
+
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+use {ChainedError, ErasedChainedError, ErrorRef};
+
+#[allow(unknown_lints, bare_trait_objects)]
+type Extract = for<'a> fn(ErrorRef<'a>) -> Option<&'a ErasedChainedError>;
+
+/// The registered error types, most recent first.
+static REGISTRATIONS: AtomicPtr<Registration> = AtomicPtr::new(ptr::null_mut());
+
+/// The `extract_erased` of an error type, in a `static` of the generated
+/// code.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Registration {
+    extract: Extract,
+    registered: AtomicBool,
+    next: AtomicPtr<Registration>,
+}
+
+impl Registration {
+    #[doc(hidden)]
+    pub const fn new(extract: Extract) -> Registration {
+        Registration {
+            extract,
+            registered: AtomicBool::new(false),
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Adds the error type to the registered ones, once.
+    #[doc(hidden)]
+    pub fn register(&'static self) {
+        if self.registered.load(Ordering::Acquire) || self.registered.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let this = self as *const Registration as *mut Registration;
+        let mut head = REGISTRATIONS.load(Ordering::Acquire);
+        loop {
+            self.next.store(head, Ordering::Relaxed);
+            match REGISTRATIONS.compare_exchange_weak(
+                head,
+                this,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+}
+
+/// Returns a type-erased view of `e` if it is a `T`, one of the error types
+/// from its `links`, or any other error generated by `error_chain!`.
+#[allow(unknown_lints, bare_trait_objects)]
+pub(crate) fn extract<'a, T: ChainedError>(e: ErrorRef<'a>) -> Option<&'a ErasedChainedError> {
+    if let Some(e) = T::extract_erased(e) {
+        return Some(e);
+    }
+    let mut registration = REGISTRATIONS.load(Ordering::Acquire);
+    // Registrations are `'static` and never removed from the list.
+    while let Some(r) = unsafe { registration.as_ref() } {
+        if let Some(e) = (r.extract)(e) {
+            return Some(e);
+        }
+        registration = r.next.load(Ordering::Acquire);
+    }
+    None
+}
@@ -1,41 +1,5 @@
 #[doc(hidden)]
 #[macro_export]
-#[cfg(not(has_error_source))]
-macro_rules! impl_error_chain_cause_or_source {
-    (
-        types {
-            $error_kind_name:ident
-        }
-
-        foreign_links {
-            $( $foreign_link_variant:ident ( $foreign_link_error_path:path )
-               $( #[$meta_foreign_links:meta] )*; )*
-        }
-    ) => {
-        #[allow(unknown_lints, renamed_and_removed_lints)]
-        #[allow(unused_doc_comment, unused_doc_comments)]
-        fn cause(&self) -> Option<&::std::error::Error> {
-            match self.1.next_error {
-                Some(ref c) => Some(&**c),
-                None => {
-                    match self.0 {
-                        $(
-                            $(#[$meta_foreign_links])*
-                            $error_kind_name::$foreign_link_variant(ref foreign_err) => {
-                                foreign_err.cause()
-                            }
-                        ) *
-                        _ => None
-                    }
-                }
-            }
-        }
-    };
-}
-
-#[cfg(has_error_source)]
-#[doc(hidden)]
-#[macro_export]
 macro_rules! impl_error_chain_cause_or_source {
     (
         types {
@@ -49,11 +13,11 @@ macro_rules! impl_error_chain_cause_or_source {
     ) => {
             #[allow(unknown_lints, renamed_and_removed_lints, bare_trait_objects)]
             #[allow(unused_doc_comment, unused_doc_comments)]
-            fn source(&self) -> Option<&(std::error::Error + 'static)> {
-                match self.1.next_error {
+            fn source(&self) -> Option<&($crate::__private::Error + 'static)> {
+                match $crate::ChainedError::state(self).next_error {
                     Some(ref c) => Some(&**c),
                     None => {
-                        match self.0 {
+                        match *self.kind() {
                         $(
                             $(#[$meta_foreign_links])*
                             $error_kind_name::$foreign_link_variant(ref foreign_err) => {
@@ -68,6 +32,180 @@ macro_rules! impl_error_chain_cause_or_source {
         };
 }
 
+/// Reports a malformed `error_chain!` invocation, from the catch-all arms of
+/// the internal macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! error_chain_compile_error {
+    (UNKNOWN_BLOCK $block:ident) => {
+        compile_error!(concat!(
+            "unknown block `",
+            stringify!($block),
+            "` in `error_chain!`; expected `types`, ",
+            "`links`, `foreign_links`, `opaque_links`, `errors`, `msg_variant`, ",
+            "`skip_msg_variant`, `thin` or `into_io`"
+        ));
+    };
+    (UNEXPECTED_TOKEN $token:tt) => {
+        compile_error!(concat!(
+            "unexpected `",
+            stringify!($token),
+            "` in `error_chain!`; expected a block such as ",
+            "`errors { ... }`"
+        ));
+    };
+    (DUPLICATE_BLOCK $block:ident) => {
+        compile_error!(concat!("duplicate `", stringify!($block), "` block"));
+    };
+    (DUPLICATE_ANNOTATION $annotation:ident) => {
+        compile_error!(concat!(
+            "duplicate `",
+            stringify!($annotation),
+            "` annotation"
+        ));
+    };
+    (CONFLICTING_ANNOTATIONS $first:ident $second:ident) => {
+        compile_error!(concat!(
+            "`",
+            stringify!($first),
+            "` cannot be combined with `",
+            stringify!($second),
+            "`"
+        ));
+    };
+    (INVALID_ANNOTATION msg_variant) => {
+        compile_error!(
+            "invalid `msg_variant` annotation; expected `msg_variant Name;`, \
+             `msg_variant Name(Type);` or `msg_variant Name without_from;`"
+        );
+    };
+    (INVALID_BLOCK types) => {
+        compile_error!(
+            "invalid `types` block; expected `Error, ErrorKind, ResultExt, Result;` or \
+             `Error, ErrorKind, ResultExt;`"
+        );
+    };
+    (INVALID_BLOCK links) => {
+        compile_error!(
+            "invalid `links` block; expected entries such as \
+             `Inner(inner::Error, inner::ErrorKind);`"
+        );
+    };
+    (INVALID_BLOCK foreign_links) => {
+        compile_error!(
+            "invalid `foreign_links` block; expected entries such as `Io(::std::io::Error);`, \
+             `Io(::std::io::Error) retryable(is_transient);` or `Io(::std::io::Error) => map_io;`"
+        );
+    };
+    (UNKNOWN_FOREIGN_ATTRIBUTE $attribute:ident) => {
+        compile_error!(concat!(
+            "unknown attribute `#[",
+            stringify!($attribute),
+            "]` before a `foreign_links` entry; expected `#[boxed]`"
+        ));
+    };
+    (BOXED_WITHOUT_FIELDS $item:ident) => {
+        compile_error!(concat!(
+            "`#[boxed]` on variant `",
+            stringify!($item),
+            "`, which has no fields"
+        ));
+    };
+    (BOXED_WITHOUT_STRUCT $item:ident) => {
+        compile_error!(concat!(
+            "`#[boxed]` on variant `",
+            stringify!($item),
+            "` needs the name of the struct holding its fields, e.g. `#[boxed(",
+            stringify!($item),
+            "Details)]`"
+        ));
+    };
+    (INVALID_BLOCK opaque_links) => {
+        compile_error!("invalid `opaque_links` block; expected a single entry such as `Upstream;`");
+    };
+    (INVALID_VARIANT $token:tt) => {
+        compile_error!(concat!(
+            "unexpected `",
+            stringify!($token),
+            "` in `errors` block; expected a variant such ",
+            "as `Name`, `Name(field: Type)` or `Name { field: Type }`"
+        ));
+    };
+    (UNKNOWN_CLAUSE $clause:ident $item:ident) => {
+        compile_error!(concat!(
+            "unknown clause `",
+            stringify!($clause),
+            "` in variant `",
+            stringify!($item),
+            "`; expected `description`, `display`, `io_kind`, `http_status`, `sensitive`, ",
+            "`severity`, `retryable`, `help`, `message_id` or `code`"
+        ));
+    };
+    (INVALID_CLAUSE $clause:ident $item:ident) => {
+        compile_error!(concat!(
+            "invalid arguments for clause `",
+            stringify!($clause),
+            "` in variant `",
+            stringify!($item),
+            "`"
+        ));
+    };
+    (UNEXPECTED_IN_VARIANT $token:tt $item:ident) => {
+        compile_error!(concat!(
+            "unexpected `",
+            stringify!($token),
+            "` in variant `",
+            stringify!($item),
+            "`; expected a clause such as `display(\"...\")`"
+        ));
+    };
+}
+
+/// The kind of an error converted from a `foreign_links` type, given by its
+/// mapping function if any.
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! impl_error_chain_foreign_kind {
+    ($e:ident, $storage:tt $variant:path) => {
+        $variant(impl_error_chain_foreign_storage!(NEW $storage $e))
+    };
+    ($e:ident, $storage:tt $variant:path, $map:expr) => {
+        ($map)($e)
+    };
+}
+
+/// How the error of a `foreign_links` variant is stored in the kind: inline,
+/// or in a `Box` with `#[boxed]`.
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! impl_error_chain_foreign_storage {
+    (CHECK []) => {};
+    (CHECK [boxed]) => {};
+    (CHECK [$storage:ident]) => {
+        error_chain_compile_error!(UNKNOWN_FOREIGN_ATTRIBUTE $storage);
+    };
+    (TYPE [boxed] $error:path) => { $crate::__private::Box<$error> };
+    (TYPE $storage:tt $error:path) => { $error };
+    (NEW [boxed] $e:ident) => { $crate::__private::Box::new($e) };
+    (NEW $storage:tt $e:ident) => { $e };
+    (REF [boxed] $e:ident) => { &**$e };
+    (REF $storage:tt $e:ident) => { $e };
+}
+
+/// Whether the error of a `foreign_links` variant is retryable, according
+/// to the function given by its `retryable` clause or to
+/// `classify::foreign_is_retryable`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_error_chain_foreign_retryable {
+    ([] $e:expr) => {
+        $crate::classify::foreign_is_retryable($e)
+    };
+    ([$classify:expr] $e:expr) => {
+        ($classify)($e)
+    };
+}
+
 /// Conditional usage of deprecated Error::description
 #[doc(hidden)]
 #[cfg(has_error_description_deprecated)]
@@ -83,10 +221,73 @@ macro_rules! call_to_deprecated_description {
 #[macro_export(local_inner_macros)]
 macro_rules! call_to_deprecated_description {
     ($e:ident) => {
-        ::std::error::Error::description($e)
+        $crate::__private::Error::description($e)
     };
 }
 
+/// Generates the conversions to and from `std::io::Error`, which need `std`.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+#[macro_export(local_inner_macros)]
+macro_rules! impl_error_chain_io {
+    (KIND $name:ident {$(
+        $item:ident: $imode:tt [$(#[$imeta:meta])*] [$( $var:ident: $typ:ty ),*] {$( $funcs:tt )*}
+    )*}) => {
+        #[allow(unknown_lints, unused, renamed_and_removed_lints)]
+        #[allow(unused_doc_comment, unused_doc_comments)]
+        impl $name {
+            /// The `std::io::ErrorKind` used when converting to `std::io::Error`,
+            /// as given by the `io_kind` clause of the variant. Defaults to
+            /// `Other`.
+            pub fn io_kind(&self) -> $crate::__private::io::ErrorKind {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_IO_KIND_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => $crate::__private::io::ErrorKind::Other,
+                }
+            }
+        }
+    };
+    (ERROR {} $error_name:ident) => {};
+    (ERROR {into_io} $error_name:ident) => {
+        impl From<$error_name> for $crate::__private::io::Error {
+            fn from(e: $error_name) -> Self {
+                let kind = e.kind().io_kind();
+                $crate::__private::io::Error::new(kind, $crate::Chained::new(e))
+            }
+        }
+
+        impl $error_name {
+            /// Recovers the error wrapped by the conversion to `std::io::Error`,
+            /// or returns the `std::io::Error` unchanged if it does not wrap
+            /// one.
+            pub fn from_io(
+                e: $crate::__private::io::Error,
+            ) -> $crate::__private::Result<$error_name, $crate::__private::io::Error> {
+                $crate::io::downcast(e)
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+#[macro_export(local_inner_macros)]
+macro_rules! impl_error_chain_io {
+    (ERROR {into_io} $error_name:ident) => {
+        compile_error!("the `into_io` annotation needs the `std` feature of error-chain");
+    };
+    ($($t:tt)*) => {};
+}
+
 /// Prefer to use `error_chain` instead of this macro.
 #[doc(hidden)]
 #[macro_export(local_inner_macros)]
@@ -98,7 +299,7 @@ macro_rules! impl_error_chain_processed {
     ) => {
         impl_error_chain_processed! {
             types {
-                Error, ErrorKind, ResultExt, Result;
+                [pub] Error, ErrorKind, ResultExt, Result;
             }
             $( $rest )*
         }
@@ -106,80 +307,170 @@ macro_rules! impl_error_chain_processed {
     // With `Result` wrapper.
     (
         types {
-            $error_name:ident, $error_kind_name:ident,
-            $result_ext_name:ident, $result_name:ident;
+            $( #[$error_meta:meta] )* [$vis:vis] $error_name:ident,
+            $( #[$($kind_meta:tt)*] )* $error_kind_name:ident,
+            $( #[$result_ext_meta:meta] )* $result_ext_name:ident,
+            $( #[$result_meta:meta] )* $result_name:ident;
         }
         $( $rest: tt )*
     ) => {
         impl_error_chain_processed! {
             types {
-                $error_name, $error_kind_name,
-                $result_ext_name;
+                $( #[$error_meta] )* [$vis] $error_name,
+                $( #[$($kind_meta)*] )* $error_kind_name,
+                $( #[$result_ext_meta] )* $result_ext_name;
             }
             $( $rest )*
         }
         /// Convenient wrapper around `std::Result`.
         #[allow(unused)]
-        pub type $result_name<T> = ::std::result::Result<T, $error_name>;
+        $( #[$result_meta] )*
+        $vis type $result_name<T> = $crate::__private::Result<T, $error_name>;
     };
 
-    // With `Msg` variant.
+    // With the default `Msg` variant.
+    (
+        types $types:tt
+        repr $repr:tt
+        io $io:tt
+        kind $kind:tt
+        opaque_links $opaque_links:tt
+        links $( $rest:tt )*
+    ) => {
+        impl_error_chain_processed! {
+            types $types
+            repr $repr
+            io $io
+            kind $kind
+            opaque_links $opaque_links
+            msg_variant Msg () from
+            links $( $rest )*
+        }
+    };
+    // With a `msg_variant` holding a `String`.
+    (
+        types $types:tt
+        repr $repr:tt
+        io $io:tt
+        kind $kind:tt
+        opaque_links $opaque_links:tt
+        msg_variant $msg_variant:ident () $from:ident
+        $( $rest:tt )*
+    ) => {
+        impl_error_chain_processed! {
+            types $types
+            repr $repr
+            io $io
+            kind $kind
+            opaque_links $opaque_links
+            msg_variant $msg_variant ($crate::__private::String) $from
+            $( $rest )*
+        }
+    };
+    // With the conversions from strings.
     (
         types {
-            $error_name:ident, $error_kind_name:ident, $($types:tt)*
+            $( #[$error_meta:meta] )* [$vis:vis] $error_name:ident,
+            $( #[$($kind_meta:tt)*] )* $error_kind_name:ident,
+            $($types:tt)*
         }
-        links $links:tt
-        foreign_links $foreign_links:tt
-        errors { $($errors:tt)* }
+        repr $repr:tt
+        io $io:tt
+        kind $kind:tt
+        opaque_links $opaque_links:tt
+        msg_variant $msg_variant:ident ($msg_type:ty) from
+        $( $rest:tt )*
     ) => {
         impl_error_chain_processed! {
             types {
-                $error_name, $error_kind_name, $($types)*
-            }
-            skip_msg_variant
-            links $links
-            foreign_links $foreign_links
-            errors {
-                /// A convenient variant for String.
-                Msg(s: String) {
-                    description(&s)
-                    display("{}", s)
-                }
-
-                $($errors)*
+                $( #[$error_meta] )* [$vis] $error_name,
+                $( #[$($kind_meta)*] )* $error_kind_name,
+                $($types)*
             }
+            repr $repr
+            io $io
+            kind $kind
+            opaque_links $opaque_links
+            msg_variant $msg_variant ($msg_type) without_from
+            $( $rest )*
         }
 
         impl<'a> From<&'a str> for $error_kind_name {
             fn from(s: &'a str) -> Self {
-                $error_kind_name::Msg(s.into())
+                $error_kind_name::$msg_variant($crate::__private::String::from(s).into())
             }
         }
 
-        impl From<String> for $error_kind_name {
-            fn from(s: String) -> Self {
-                $error_kind_name::Msg(s)
+        impl From<$crate::__private::String> for $error_kind_name {
+            #[allow(unknown_lints, clippy::useless_conversion)]
+            fn from(s: $crate::__private::String) -> Self {
+                $error_kind_name::$msg_variant(s.into())
             }
         }
 
         impl<'a> From<&'a str> for $error_name {
+            #[track_caller]
             fn from(s: &'a str) -> Self {
                 Self::from_kind(s.into())
             }
         }
 
-        impl From<String> for $error_name {
-            fn from(s: String) -> Self {
+        impl From<$crate::__private::String> for $error_name {
+            #[track_caller]
+            fn from(s: $crate::__private::String) -> Self {
                 Self::from_kind(s.into())
             }
         }
     };
+    // The variant itself.
+    (
+        types $types:tt
+        repr $repr:tt
+        io $io:tt
+        kind $kind:tt
+        opaque_links $opaque_links:tt
+        msg_variant $msg_variant:ident ($msg_type:ty) without_from
+        links $links:tt
+        foreign_links $foreign_links:tt
+        errors { $($errors:tt)* }
+    ) => {
+        impl_error_chain_processed! {
+            types $types
+            repr $repr
+            io $io
+            kind $kind
+            opaque_links $opaque_links
+            skip_msg_variant
+            links $links
+            foreign_links $foreign_links
+            errors {
+                /// A convenient variant for String.
+                $msg_variant(s: $msg_type) {
+                    description(&s)
+                    display("{}", s)
+                }
+
+                $($errors)*
+            }
+        }
+    };
 
     // Without `Result` wrapper or `Msg` variant.
     (
         types {
-            $error_name:ident, $error_kind_name:ident,
-            $result_ext_name:ident;
+            $( #[$error_meta:meta] )* [$vis:vis] $error_name:ident,
+            $( #[$($kind_meta:tt)*] )* $error_kind_name:ident,
+            $( #[$result_ext_meta:meta] )* $result_ext_name:ident;
+        }
+
+        repr $repr:tt
+
+        io $io:tt
+
+        kind { $( $kind_mode:ident )* }
+
+        opaque_links {
+            $( $opaque_variant:ident $( #[$meta_opaque:meta] )*; )?
         }
 
         skip_msg_variant
@@ -191,7 +482,9 @@ macro_rules! impl_error_chain_processed {
 
         foreign_links {
             $( $foreign_link_variant:ident ( $foreign_link_error_path:path )
-               $( #[$meta_foreign_links:meta] )*; )*
+               $foreign_link_storage:tt $foreign_link_classify:tt
+               $( #[$meta_foreign_links:meta] )*
+               $( => $foreign_link_map:expr )?; )*
         }
 
         errors {
@@ -199,52 +492,32 @@ macro_rules! impl_error_chain_processed {
         }
 
     ) => {
-        /// The Error type.
-        ///
-        /// This tuple struct is made of two elements:
-        ///
-        /// - an `ErrorKind` which is used to determine the type of the error.
-        /// - An internal `State`, not meant for direct use outside of `error_chain`
-        ///   internals, containing:
-        ///   - a backtrace, generated when the error is created.
-        ///   - an error chain, used for the implementation of `Error::cause()`.
-        #[derive(Debug)]
-        pub struct $error_name(
-            // The members must be `pub` for `links`.
-            /// The kind of the error.
-            pub $error_kind_name,
-            /// Contains the error chain and the backtrace.
-            #[doc(hidden)]
-            pub $crate::State,
-        );
+        impl_error_chain_repr!(STRUCT $repr [$vis] $error_name $error_kind_name $( #[$error_meta] )*);
 
         impl $crate::ChainedError for $error_name {
             type ErrorKind = $error_kind_name;
 
-            fn new(kind: $error_kind_name, state: $crate::State) -> $error_name {
-                $error_name(kind, state)
-            }
+            impl_error_chain_repr!(METHODS $repr $error_name $error_kind_name);
 
+            #[track_caller]
             fn from_kind(kind: Self::ErrorKind) -> Self {
                 Self::from_kind(kind)
             }
 
+            #[track_caller]
             fn with_chain<E, K>(error: E, kind: K)
                 -> Self
-                where E: ::std::error::Error + Send + 'static,
+                where E: $crate::__private::Error + Send + 'static,
                       K: Into<Self::ErrorKind>
             {
                 Self::with_chain(error, kind)
             }
 
-            fn kind(&self) -> &Self::ErrorKind {
-                self.kind()
-            }
-
-            fn iter(&self) -> $crate::Iter {
+            fn iter(&self) -> $crate::Iter<'_> {
                 $crate::Iter::new(Some(self))
             }
 
+            #[track_caller]
             fn chain_err<F, EK>(self, error: F) -> Self
                 where F: FnOnce() -> EK,
                       EK: Into<$error_kind_name> {
@@ -258,68 +531,92 @@ macro_rules! impl_error_chain_processed {
             impl_extract_backtrace!($error_name
                                     $error_kind_name
                                     $([$link_error_path, $(#[$meta_links])*])*);
+
+            impl_extract_erased!($error_name
+                                 $([$link_error_path, $(#[$meta_links])*])*);
         }
 
         #[allow(dead_code)]
         impl $error_name {
             /// Constructs an error from a kind, and generates a backtrace.
+            #[track_caller]
             pub fn from_kind(kind: $error_kind_name) -> $error_name {
-                $error_name(
-                    kind,
-                    $crate::State::default(),
-                )
+                $crate::metrics::notify::<$error_name, _>(|| kind.variant_name());
+                $crate::ChainedError::new(kind, $crate::State::default())
             }
 
             /// Constructs a chained error from another error and a kind, and generates a backtrace.
+            #[track_caller]
             pub fn with_chain<E, K>(error: E, kind: K)
                 -> $error_name
-                where E: ::std::error::Error + Send + 'static,
+                where E: $crate::__private::Error + Send + 'static,
                       K: Into<$error_kind_name>
             {
-                $error_name::with_boxed_chain(Box::new(error), kind)
+                $error_name::with_boxed_chain($crate::__private::Box::new(error), kind)
             }
 
             /// Construct a chained error from another boxed error and a kind, and generates a backtrace
             #[allow(unknown_lints, bare_trait_objects)]
-            pub fn with_boxed_chain<K>(error: Box<::std::error::Error + Send>, kind: K)
+            #[track_caller]
+            pub fn with_boxed_chain<K>(error: $crate::__private::Box<$crate::__private::Error + Send>, kind: K)
                 -> $error_name
                 where K: Into<$error_kind_name>
             {
-                $error_name(
-                    kind.into(),
-                    $crate::State::new::<$error_name>(error, ),
-                )
+                let kind = kind.into();
+                $crate::metrics::notify::<$error_name, _>(|| kind.variant_name());
+                $crate::ChainedError::new(kind, $crate::State::new::<$error_name>(error))
             }
 
             /// Returns the kind of the error.
             pub fn kind(&self) -> &$error_kind_name {
-                &self.0
+                $crate::ChainedError::kind(self)
             }
 
             /// Iterates over the error chain.
-            pub fn iter(&self) -> $crate::Iter {
+            pub fn iter(&self) -> $crate::Iter<'_> {
                 $crate::ChainedError::iter(self)
             }
 
             /// Returns the backtrace associated with this error.
             pub fn backtrace(&self) -> Option<&$crate::Backtrace> {
-                self.1.backtrace()
+                $crate::ChainedError::state(self).backtrace()
             }
 
             /// Extends the error chain with a new entry.
+            #[track_caller]
             pub fn chain_err<F, EK>(self, error: F) -> $error_name
                 where F: FnOnce() -> EK, EK: Into<$error_kind_name> {
-                $error_name::with_chain(self, Self::from_kind(error().into()))
+                $error_name::with_chain(self, error())
+            }
+
+            /// Adds a hint on how to fix the error, rendered after the
+            /// causes by `display_chain`.
+            pub fn with_help<S>(self, help: S) -> $error_name
+                where S: Into<$crate::__private::String> {
+                let (kind, mut state) = $crate::ChainedError::into_parts(self);
+                state.help.get_or_insert_with(Default::default).push(help.into());
+                $crate::ChainedError::new(kind, state)
+            }
+
+            /// The hint given by the `help` clause of the variant, followed
+            /// by the ones added with `with_help`.
+            pub fn help(&self) -> $crate::__private::Vec<$crate::__private::String> {
+                let mut help = $crate::__private::Vec::new();
+                help.extend(self.kind().help());
+                if let Some(ref hints) = $crate::ChainedError::state(self).help {
+                    help.extend(hints.iter().cloned());
+                }
+                help
             }
 
             /// A short description of the error.
             /// This method is identical to [`Error::description()`](https://doc.rust-lang.org/nightly/std/error/trait.Error.html#tymethod.description)
             pub fn description(&self) -> &str {
-                self.0.description()
+                self.kind().description()
             }
         }
 
-        impl ::std::error::Error for $error_name {
+        impl $crate::__private::Error for $error_name {
             #[cfg(not(has_error_description_deprecated))]
             fn description(&self) -> &str {
                 self.description()
@@ -336,9 +633,45 @@ macro_rules! impl_error_chain_processed {
             }
         }
 
-        impl ::std::fmt::Display for $error_name {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                ::std::fmt::Display::fmt(&self.0, f)
+        #[allow(unknown_lints, renamed_and_removed_lints, bare_trait_objects)]
+        #[allow(unused_doc_comment, unused_doc_comments)]
+        impl $crate::ErasedChainedError for $error_name {
+            fn foreign_error(&self) -> Option<&($crate::__private::Error + Send + 'static)> {
+                match *self.kind() {
+                    $(
+                        $(#[$meta_foreign_links])*
+                        $error_kind_name::$foreign_link_variant(ref foreign_err) => {
+                            Some(impl_error_chain_foreign_storage!(
+                                REF $foreign_link_storage foreign_err
+                            ))
+                        }
+                    ) *
+                    _ => None
+                }
+            }
+
+            fn localize(&self, localizer: &$crate::localize::Localizer)
+                -> $crate::__private::Option<$crate::__private::String>
+            {
+                self.kind().localize(localizer)
+            }
+
+            fn severity(&self) -> $crate::classify::Severity {
+                self.kind().severity()
+            }
+
+            fn is_retryable(&self) -> bool {
+                self.kind().is_retryable()
+            }
+
+            fn help(&self) -> $crate::__private::Vec<$crate::__private::String> {
+                self.help()
+            }
+        }
+
+        impl $crate::__private::fmt::Display for $error_name {
+            fn fmt(&self, f: &mut $crate::__private::fmt::Formatter) -> $crate::__private::fmt::Result {
+                $crate::__private::fmt::Display::fmt(self.kind(), f)
             }
         }
 
@@ -346,10 +679,8 @@ macro_rules! impl_error_chain_processed {
             $(#[$meta_links])*
             impl From<$link_error_path> for $error_name {
                 fn from(e: $link_error_path) -> Self {
-                    $error_name(
-                        $error_kind_name::$link_variant(e.0),
-                        e.1,
-                    )
+                    let (kind, state) = $crate::ChainedError::into_parts(e);
+                    $crate::ChainedError::new($error_kind_name::$link_variant(kind), state)
                 }
             }
         ) *
@@ -357,43 +688,100 @@ macro_rules! impl_error_chain_processed {
         $(
             $(#[$meta_foreign_links])*
             impl From<$foreign_link_error_path> for $error_name {
+                #[track_caller]
                 fn from(e: $foreign_link_error_path) -> Self {
-                    $error_name::from_kind(
-                        $error_kind_name::$foreign_link_variant(e)
-                    )
+                    $error_name::from_kind(impl_error_chain_foreign_kind!(
+                        e,
+                        $foreign_link_storage $error_kind_name::$foreign_link_variant
+                        $( , $foreign_link_map )?
+                    ))
                 }
             }
         ) *
 
         impl From<$error_kind_name> for $error_name {
+            #[track_caller]
             fn from(e: $error_kind_name) -> Self {
                 $error_name::from_kind(e)
             }
         }
 
+        impl $crate::HttpStatus for $error_name {
+            fn http_status(&self) -> u16 {
+                $crate::HttpStatus::http_status(self.kind())
+            }
+        }
+
+        impl_error_chain_io!(ERROR $io $error_name);
+
+        $( impl_error_chain_foreign_storage!(CHECK $foreign_link_storage); )*
+
+        $(
+            $(#[$meta_opaque])*
+            impl $error_name {
+                /// Wraps an error of any other `error_chain!` type in the
+                /// `opaque_links` variant, keeping its cause and backtrace.
+                pub fn from_chained<E>(e: E) -> Self
+                    where E: $crate::ChainedError,
+                          E::ErrorKind: $crate::ChainedErrorKind + Send + 'static,
+                {
+                    let (kind, state) = $crate::ChainedError::into_parts(e);
+                    $crate::ChainedError::new(
+                        $error_kind_name::$opaque_variant($crate::OpaqueKind::new(kind)),
+                        state,
+                    )
+                }
+            }
+        )?
+
         // The ErrorKind type
         // --------------
 
         impl_error_chain_kind! {
+            $( $kind_mode )*
             /// The kind of an error.
-            #[derive(Debug)]
-            pub enum $error_kind_name {
+            $( #[$($kind_meta)*] )*
+            $vis enum $error_kind_name {
                 $(
                     $(#[$meta_links])*
                     $link_variant(e: $link_kind_path) {
                         description(e.description())
                         display("{}", e)
+                        io_kind(e.io_kind())
+                        http_status($crate::HttpStatus::http_status(e))
+                        linked(e)
                     }
                 ) *
 
                 $(
                     $(#[$meta_foreign_links])*
-                    $foreign_link_variant(err: $foreign_link_error_path) {
+                    $foreign_link_variant(
+                        err: impl_error_chain_foreign_storage!(
+                            TYPE $foreign_link_storage $foreign_link_error_path
+                        )
+                    ) {
                         description(call_to_deprecated_description!(err))
                         display("{}", err)
+                        io_kind($crate::io::foreign_io_kind(
+                            impl_error_chain_foreign_storage!(REF $foreign_link_storage err)
+                        ))
+                        retryable(impl_error_chain_foreign_retryable!(
+                            $foreign_link_classify
+                            impl_error_chain_foreign_storage!(REF $foreign_link_storage err)
+                        ))
                     }
                 ) *
 
+                $(
+                    $(#[$meta_opaque])*
+                    $opaque_variant(kind: $crate::OpaqueKind) {
+                        description(kind.description())
+                        display("{}", kind)
+                        http_status($crate::HttpStatus::http_status(kind))
+                        linked(kind)
+                    }
+                )?
+
                 $($error_chunks)*
             }
         }
@@ -409,41 +797,117 @@ macro_rules! impl_error_chain_processed {
 
         impl From<$error_name> for $error_kind_name {
             fn from(e: $error_name) -> Self {
-                e.0
+                $crate::ChainedError::into_parts(e).0
+            }
+        }
+
+        #[allow(unknown_lints, renamed_and_removed_lints)]
+        #[allow(unused_doc_comment, unused_doc_comments)]
+        impl $error_kind_name {
+            /// Like `variant_name`, but continues into the linked kind for
+            /// `links` variants, e.g. `"Inner::Timeout"`.
+            pub fn variant_path(&self) -> $crate::__private::String {
+                match *self {
+                    $(
+                        $(#[$meta_links])*
+                        $error_kind_name::$link_variant(ref e) => {
+                            let mut path = $crate::__private::String::from(stringify_internal!($link_variant));
+                            path.push_str("::");
+                            path.push_str(&e.variant_path());
+                            path
+                        }
+                    ) *
+                    $(
+                        $(#[$meta_opaque])*
+                        $error_kind_name::$opaque_variant(ref kind) => {
+                            let mut path = $crate::__private::String::from(stringify_internal!($opaque_variant));
+                            path.push_str("::");
+                            path.push_str(&kind.variant_path());
+                            path
+                        }
+                    )?
+                    _ => $crate::__private::String::from(self.variant_name()),
+                }
+            }
+        }
+
+        impl $crate::ChainedErrorKind for $error_kind_name {
+            fn description(&self) -> &str {
+                self.description()
+            }
+
+            fn variant_name(&self) -> &'static str {
+                self.variant_name()
+            }
+
+            fn variant_path(&self) -> $crate::__private::String {
+                self.variant_path()
+            }
+
+            fn message_id(&self) -> $crate::__private::Option<&'static str> {
+                self.message_id()
+            }
+
+            fn code(&self) -> $crate::__private::Option<&'static str> {
+                self.code()
+            }
+
+            #[allow(unknown_lints, bare_trait_objects)]
+            fn localize(&self, localizer: &$crate::localize::Localizer)
+                -> $crate::__private::Option<$crate::__private::String>
+            {
+                self.localize(localizer)
+            }
+
+            fn severity(&self) -> $crate::classify::Severity {
+                self.severity()
+            }
+
+            fn is_retryable(&self) -> bool {
+                self.is_retryable()
+            }
+
+            fn help(&self) -> $crate::__private::Option<$crate::__private::String> {
+                self.help()
             }
         }
 
         // The ResultExt trait defines the `chain_err` method.
 
         /// Additional methods for `Result`, for easy interaction with this crate.
-        pub trait $result_ext_name<T> {
+        $( #[$result_ext_meta] )*
+        $vis trait $result_ext_name<T> {
             /// If the `Result` is an `Err` then `chain_err` evaluates the closure,
             /// which returns *some type that can be converted to `ErrorKind`*, boxes
             /// the original error to store as the cause, then returns a new error
             /// containing the original error.
-            fn chain_err<F, EK>(self, callback: F) -> ::std::result::Result<T, $error_name>
+            fn chain_err<F, EK>(self, callback: F) -> $crate::__private::Result<T, $error_name>
                 where F: FnOnce() -> EK,
                       EK: Into<$error_kind_name>;
         }
 
-        impl<T, E> $result_ext_name<T> for ::std::result::Result<T, E> where E: ::std::error::Error + Send + 'static {
-            fn chain_err<F, EK>(self, callback: F) -> ::std::result::Result<T, $error_name>
+        impl<T, E> $result_ext_name<T> for $crate::__private::Result<T, E> where E: $crate::__private::Error + Send + 'static {
+            // Not written with `map_err`, closures can't track the caller.
+            #[track_caller]
+            fn chain_err<F, EK>(self, callback: F) -> $crate::__private::Result<T, $error_name>
                 where F: FnOnce() -> EK,
                       EK: Into<$error_kind_name> {
-                self.map_err(move |e| {
-                    let state = $crate::State::new::<$error_name>(Box::new(e), );
-                    $crate::ChainedError::new(callback().into(), state)
-                })
+                match self {
+                    Ok(v) => Ok(v),
+                    Err(e) => Err($error_name::with_chain(e, callback())),
+                }
             }
         }
 
-        impl<T> $result_ext_name<T> for ::std::option::Option<T> {
-            fn chain_err<F, EK>(self, callback: F) -> ::std::result::Result<T, $error_name>
+        impl<T> $result_ext_name<T> for $crate::__private::Option<T> {
+            #[track_caller]
+            fn chain_err<F, EK>(self, callback: F) -> $crate::__private::Result<T, $error_name>
                 where F: FnOnce() -> EK,
                       EK: Into<$error_kind_name> {
-                self.ok_or_else(move || {
-                    $crate::ChainedError::from_kind(callback().into())
-                })
+                match self {
+                    Some(v) => Ok(v),
+                    None => Err($error_name::from_kind(callback().into())),
+                }
             }
         }
 
@@ -455,38 +919,107 @@ macro_rules! impl_error_chain_processed {
 #[doc(hidden)]
 #[macro_export(local_inner_macros)]
 macro_rules! error_chain_processing {
+    // Without a visibility, the types are public.
     (
         ({}, $($rest:tt)*)
-        types $content:tt
+        types {
+            $(
+                $( #[$error_meta:meta] )* $error:ident,
+                $( #[$($kind_meta:tt)*] )* $kind:ident,
+                $( #[$result_ext_meta:meta] )* $result_ext:ident
+                $( , $( #[$result_meta:meta] )* $result:ident )?;
+            )?
+        }
         $( $tail:tt )*
     ) => {
         error_chain_processing! {
-            ($content, $($rest)*)
+            ({
+                $(
+                    $( #[$error_meta] )* [pub] $error,
+                    $( #[$($kind_meta)*] )* $kind,
+                    $( #[$result_ext_meta] )* $result_ext
+                    $( , $( #[$result_meta] )* $result )?;
+                )?
+            }, $($rest)*)
             $($tail)*
         }
     };
+    (
+        ({}, $($rest:tt)*)
+        types {
+            $( #[$error_meta:meta] )* $vis:vis $error:ident,
+            $( #[$($kind_meta:tt)*] )* $kind:ident,
+            $( #[$result_ext_meta:meta] )* $result_ext:ident
+            $( , $( #[$result_meta:meta] )* $result:ident )?;
+        }
+        $( $tail:tt )*
+    ) => {
+        error_chain_processing! {
+            ({
+                $( #[$error_meta] )* [$vis] $error,
+                $( #[$($kind_meta)*] )* $kind,
+                $( #[$result_ext_meta] )* $result_ext
+                $( , $( #[$result_meta] )* $result )?;
+            }, $($rest)*)
+            $($tail)*
+        }
+    };
+    (
+        ({}, $($rest:tt)*)
+        types $content:tt
+        $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(INVALID_BLOCK types);
+    };
 
     (
         ($a:tt, {}, $($rest:tt)*)
-        links $content:tt
+        links {
+            $( $variant:ident ( $error:path, $kind:path ) $( #[$meta:meta] )*; )*
+        }
         $( $tail:tt )*
     ) => {
         error_chain_processing! {
-            ($a, $content, $($rest)*)
+            ($a, { $( $variant ( $error, $kind ) $( #[$meta] )*; )* }, $($rest)*)
             $($tail)*
         }
     };
+    (
+        ($a:tt, {}, $($rest:tt)*)
+        links $content:tt
+        $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(INVALID_BLOCK links);
+    };
 
     (
         ($a:tt, $b:tt, {}, $($rest:tt)*)
-        foreign_links $content:tt
+        foreign_links {
+            $(
+                $( #[$storage:ident] )?
+                $variant:ident ( $error:path ) $( #[$meta:meta] )*
+                $( retryable ( $classify:expr ) )? $( => $map:expr )?;
+            )*
+        }
         $( $tail:tt )*
     ) => {
         error_chain_processing! {
-            ($a, $b, $content, $($rest)*)
+            ($a, $b, {
+                $(
+                    $variant ( $error ) [$( $storage )?] [$( $classify )?]
+                    $( #[$meta] )* $( => $map )?;
+                )*
+            }, $($rest)*)
             $($tail)*
         }
     };
+    (
+        ($a:tt, $b:tt, {}, $($rest:tt)*)
+        foreign_links $content:tt
+        $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(INVALID_BLOCK foreign_links);
+    };
 
     (
         ($a:tt, $b:tt, $c:tt, {}, $($rest:tt)*)
@@ -509,16 +1042,134 @@ macro_rules! error_chain_processing {
             $($tail)*
         }
     };
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, {}, $($rest:tt)*)
+        msg_variant $variant:ident $( ( $ty:ty ) )? without_from;
+        $( $tail:tt )*
+    ) => {
+        error_chain_processing! {
+            ($a, $b, $c, $d, {msg_variant $variant ($( $ty )?) without_from}, $($rest)*)
+            $($tail)*
+        }
+    };
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, {}, $($rest:tt)*)
+        msg_variant $variant:ident $( ( $ty:ty ) )?;
+        $( $tail:tt )*
+    ) => {
+        error_chain_processing! {
+            ($a, $b, $c, $d, {msg_variant $variant ($( $ty )?) from}, $($rest)*)
+            $($tail)*
+        }
+    };
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, {}, $($rest:tt)*)
+        msg_variant $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(INVALID_ANNOTATION msg_variant);
+    };
+
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, $e:tt, {}, $($rest:tt)*)
+        thin
+        $( $tail:tt )*
+    ) => {
+        error_chain_processing! {
+            ($a, $b, $c, $d, $e, {thin}, $($rest)*)
+            $($tail)*
+        }
+    };
 
-    ( ($a:tt, $b:tt, $c:tt, $d:tt, {$($e:tt)*},) ) => {
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, $e:tt, $f:tt, {}, $($rest:tt)*)
+        opaque_links { $( $variant:ident $( #[$meta:meta] )*; )? }
+        $( $tail:tt )*
+    ) => {
+        error_chain_processing! {
+            ($a, $b, $c, $d, $e, $f, { $( $variant $( #[$meta] )*; )? }, $($rest)*)
+            $($tail)*
+        }
+    };
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, $e:tt, $f:tt, {}, $($rest:tt)*)
+        opaque_links $content:tt
+        $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(INVALID_BLOCK opaque_links);
+    };
+
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, $e:tt, $f:tt, $g:tt, {}, $($rest:tt)*)
+        into_io
+        $( $tail:tt )*
+    ) => {
+        error_chain_processing! {
+            ($a, $b, $c, $d, $e, $f, $g, {into_io}, $($rest)*)
+            $($tail)*
+        }
+    };
+
+    // The slot of the block is already filled.
+    ( $slots:tt types $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_BLOCK types);
+    };
+    ( $slots:tt links $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_BLOCK links);
+    };
+    ( $slots:tt foreign_links $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_BLOCK foreign_links);
+    };
+    ( $slots:tt errors $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_BLOCK errors);
+    };
+    ( $slots:tt opaque_links $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_BLOCK opaque_links);
+    };
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, {skip_msg_variant}, $($rest:tt)*)
+        msg_variant $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(CONFLICTING_ANNOTATIONS msg_variant skip_msg_variant);
+    };
+    (
+        ($a:tt, $b:tt, $c:tt, $d:tt, {msg_variant $( $e:tt )*}, $($rest:tt)*)
+        skip_msg_variant $( $tail:tt )*
+    ) => {
+        error_chain_compile_error!(CONFLICTING_ANNOTATIONS msg_variant skip_msg_variant);
+    };
+    ( $slots:tt msg_variant $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_ANNOTATION msg_variant);
+    };
+    ( $slots:tt skip_msg_variant $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_ANNOTATION skip_msg_variant);
+    };
+    ( $slots:tt thin $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_ANNOTATION thin);
+    };
+    ( $slots:tt into_io $( $tail:tt )* ) => {
+        error_chain_compile_error!(DUPLICATE_ANNOTATION into_io);
+    };
+
+    ( ($a:tt, $b:tt, $c:tt, $d:tt, {$($e:tt)*}, $f:tt, $g:tt, $h:tt,) ) => {
         impl_error_chain_processed! {
             types $a
+            repr $f
+            io $h
+            kind {}
+            opaque_links $g
             $($e)*
             links $b
             foreign_links $c
             errors $d
         }
     };
+
+    ( $slots:tt $block:ident $( $tail:tt )* ) => {
+        error_chain_compile_error!(UNKNOWN_BLOCK $block);
+    };
+    ( $slots:tt $token:tt $( $tail:tt )* ) => {
+        error_chain_compile_error!(UNEXPECTED_TOKEN $token);
+    };
 }
 
 /// Macro for generating error types and traits. See crate level documentation for details.
@@ -526,7 +1177,7 @@ macro_rules! error_chain_processing {
 macro_rules! error_chain {
     ( $($args:tt)* ) => {
         error_chain_processing! {
-            ({}, {}, {}, {}, {},)
+            ({}, {}, {}, {}, {}, {}, {}, {},)
             $($args)*
         }
     };
@@ -545,16 +1196,16 @@ macro_rules! impl_extract_backtrace {
      $([$link_error_path: path, $(#[$meta_links: meta])*])*) => {
         #[allow(unknown_lints, renamed_and_removed_lints, bare_trait_objects)]
         #[allow(unused_doc_comment, unused_doc_comments)]
-        fn extract_backtrace(e: &(::std::error::Error + Send + 'static))
+        fn extract_backtrace(e: &($crate::__private::Error + Send + 'static))
             -> Option<$crate::InternalBacktrace> {
             if let Some(e) = e.downcast_ref::<$error_name>() {
-                return Some(e.1.backtrace.clone());
+                return Some($crate::ChainedError::state(e).backtrace.clone());
             }
             $(
                 $( #[$meta_links] )*
                 {
                     if let Some(e) = e.downcast_ref::<$link_error_path>() {
-                        return Some(e.1.backtrace.clone());
+                        return Some($crate::ChainedError::state(e).backtrace.clone());
                     }
                 }
             ) *
@@ -562,3 +1213,115 @@ macro_rules! impl_extract_backtrace {
         }
     }
 }
+
+/// Downcasts an error of the chain to this error type or one of the linked
+/// error types, see `ChainedError::extract_erased`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_extract_erased {
+    ($error_name: ident
+     $([$link_error_path: path, $(#[$meta_links: meta])*])*) => {
+        #[allow(unknown_lints, renamed_and_removed_lints, bare_trait_objects)]
+        #[allow(unused_doc_comment, unused_doc_comments)]
+        fn extract_erased<'a>(e: &'a ($crate::__private::Error + 'static))
+            -> Option<&'a $crate::ErasedChainedError> {
+            if let Some(e) = e.downcast_ref::<$error_name>() {
+                return Some(e);
+            }
+            $(
+                $( #[$meta_links] )*
+                {
+                    if let Some(e) = e.downcast_ref::<$link_error_path>() {
+                        return Some(e);
+                    }
+                }
+            ) *
+            None
+        }
+    }
+}
+
+/// Registers the error type the first time one of its values is created, so
+/// that it is recognized in the chains of other error types.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_error_chain_register {
+    ($error_name:ident) => {{
+        static REGISTRATION: $crate::Registration =
+            $crate::Registration::new(<$error_name as $crate::ChainedError>::extract_erased);
+        REGISTRATION.register();
+    }};
+}
+
+/// Generates the `Error` struct and the methods of `ChainedError` which
+/// depend on its representation.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_error_chain_repr {
+    (STRUCT {} [$vis:vis] $error_name:ident $error_kind_name:ident $( #[$meta:meta] )*) => {
+        /// The Error type.
+        ///
+        /// This tuple struct is made of two elements:
+        ///
+        /// - an `ErrorKind` which is used to determine the type of the error.
+        /// - An internal `State`, not meant for direct use outside of `error_chain`
+        ///   internals, containing:
+        ///   - a backtrace, generated when the error is created.
+        ///   - an error chain, used for the implementation of `Error::cause()`.
+        #[derive(Debug)]
+        $( #[$meta] )*
+        $vis struct $error_name(
+            /// The kind of the error.
+            pub $error_kind_name,
+            /// Contains the error chain and the backtrace.
+            #[doc(hidden)]
+            pub $crate::State,
+        );
+    };
+    (STRUCT {thin} [$vis:vis] $error_name:ident $error_kind_name:ident $( #[$meta:meta] )*) => {
+        /// The Error type.
+        ///
+        /// The `ErrorKind` and the internal `State` are stored behind a single
+        /// pointer, which keeps `Result<T, Error>` small. Use `kind()` to
+        /// access the kind.
+        #[derive(Debug)]
+        $( #[$meta] )*
+        $vis struct $error_name($crate::__private::Box<($error_kind_name, $crate::State)>);
+    };
+    (METHODS {} $error_name:ident $error_kind_name:ident) => {
+        fn new(kind: $error_kind_name, state: $crate::State) -> $error_name {
+            impl_error_chain_register!($error_name);
+            $error_name(kind, state)
+        }
+
+        fn kind(&self) -> &$error_kind_name {
+            &self.0
+        }
+
+        fn state(&self) -> &$crate::State {
+            &self.1
+        }
+
+        fn into_parts(self) -> ($error_kind_name, $crate::State) {
+            (self.0, self.1)
+        }
+    };
+    (METHODS {thin} $error_name:ident $error_kind_name:ident) => {
+        fn new(kind: $error_kind_name, state: $crate::State) -> $error_name {
+            impl_error_chain_register!($error_name);
+            $error_name($crate::__private::Box::new((kind, state)))
+        }
+
+        fn kind(&self) -> &$error_kind_name {
+            &(self.0).0
+        }
+
+        fn state(&self) -> &$crate::State {
+            &(self.0).1
+        }
+
+        fn into_parts(self) -> ($error_kind_name, $crate::State) {
+            *self.0
+        }
+    };
+}
@@ -0,0 +1,218 @@
+//! Mapping errors to HTTP responses.
+//!
+//! The `http_status` clause of a variant gives the status code returned by
+//! [`HttpStatus::http_status`] for its kind, `500` by default. `links` and
+//! `opaque_links` variants use the status of the linked kind.
+//!
+//! [`Problem`] renders an error as an RFC 7807 `application/problem+json`
+//! body, without depending on a web framework:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::http::Problem;
+//! use error_chain::HttpStatus;
+//!
+//! error_chain! {
+//!     errors {
+//!         UserNotFound(id: u32) {
+//!             description("user not found")
+//!             display("no user with id {}", id)
+//!             http_status(404)
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let e = Error::from("connection refused").chain_err(|| ErrorKind::UserNotFound(7));
+//! assert_eq!(e.http_status(), 404);
+//!
+//! let problem = Problem::new(&e);
+//! assert_eq!(problem.content_type(), "application/problem+json");
+//! assert_eq!(
+//!     problem.to_json(),
+//!     r#"{"type":"about:blank","title":"Not Found","status":404,"detail":"no user with id 7","causes":["connection refused"]}"#
+//! );
+//! assert_eq!(
+//!     problem.hide_internal(true).to_json(),
+//!     r#"{"type":"about:blank","title":"Not Found","status":404,"detail":"no user with id 7"}"#
+//! );
+//! # }
+//! ```
+//!
+//! [`HttpStatus::http_status`]: trait.HttpStatus.html#tymethod.http_status
+//! [`Problem`]: struct.Problem.html
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use std::fmt::Write;
+
+use ChainedError;
+
+/// The HTTP status code corresponding to an error.
+///
+/// It is implemented for the `Error` and `ErrorKind` types generated by
+/// `error_chain!`.
+pub trait HttpStatus {
+    /// The status code, e.g. `404`.
+    fn http_status(&self) -> u16;
+}
+
+/// An RFC 7807 problem details object, describing an error to an HTTP
+/// client.
+///
+/// `title` is the reason phrase of the status, `detail` the message of the
+/// error, and the `causes` extension member lists the messages of the rest
+/// of the chain.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    type_uri: String,
+    title: String,
+    status: u16,
+    detail: String,
+    instance: Option<String>,
+    causes: Vec<String>,
+    hide_internal: bool,
+}
+
+impl Problem {
+    /// Describes an error.
+    pub fn new<E>(e: &E) -> Problem
+    where
+        E: ChainedError + HttpStatus,
+    {
+        let status = e.http_status();
+        Problem {
+            type_uri: "about:blank".into(),
+            title: reason_phrase(status).unwrap_or("Error").into(),
+            status,
+            detail: e.to_string(),
+            instance: None,
+            causes: e.iter().skip(1).map(|e| e.to_string()).collect(),
+            hide_internal: false,
+        }
+    }
+
+    /// Sets the `type` member, `about:blank` by default.
+    pub fn type_uri<S: Into<String>>(mut self, type_uri: S) -> Problem {
+        self.type_uri = type_uri.into();
+        self
+    }
+
+    /// Sets the `title` member, the reason phrase of the status by default.
+    pub fn title<S: Into<String>>(mut self, title: S) -> Problem {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the `instance` member, absent by default.
+    pub fn instance<S: Into<String>>(mut self, instance: S) -> Problem {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Hides what clients should not see: the causes, and the message of
+    /// the error itself for server errors (`5xx`).
+    pub fn hide_internal(mut self, hide: bool) -> Problem {
+        self.hide_internal = hide;
+        self
+    }
+
+    /// The status code.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The media type of the body.
+    pub fn content_type(&self) -> &'static str {
+        "application/problem+json"
+    }
+
+    /// Renders the body.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\"type\":");
+        push_json_string(&mut json, &self.type_uri);
+        json.push_str(",\"title\":");
+        push_json_string(&mut json, &self.title);
+        write!(json, ",\"status\":{}", self.status).unwrap();
+        if !self.hide_internal || self.status < 500 {
+            json.push_str(",\"detail\":");
+            push_json_string(&mut json, &self.detail);
+        }
+        if let Some(ref instance) = self.instance {
+            json.push_str(",\"instance\":");
+            push_json_string(&mut json, instance);
+        }
+        if !self.hide_internal && !self.causes.is_empty() {
+            json.push_str(",\"causes\":[");
+            for (i, cause) in self.causes.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                push_json_string(&mut json, cause);
+            }
+            json.push(']');
+        }
+        json.push('}');
+        json
+    }
+}
+
+pub(crate) fn push_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// The reason phrase of the common status codes.
+fn reason_phrase(status: u16) -> Option<&'static str> {
+    Some(match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        402 => "Payment Required",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Content Too Large",
+        414 => "URI Too Long",
+        415 => "Unsupported Media Type",
+        416 => "Range Not Satisfiable",
+        417 => "Expectation Failed",
+        421 => "Misdirected Request",
+        422 => "Unprocessable Content",
+        423 => "Locked",
+        424 => "Failed Dependency",
+        425 => "Too Early",
+        426 => "Upgrade Required",
+        428 => "Precondition Required",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        451 => "Unavailable For Legal Reasons",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        505 => "HTTP Version Not Supported",
+        507 => "Insufficient Storage",
+        508 => "Loop Detected",
+        511 => "Network Authentication Required",
+        _ => return None,
+    })
+}
@@ -1,7 +1,7 @@
-/// From https://github.com/tailhook/quick-error
-/// Changes:
-///   - replace `impl Error` by `impl Item::description`
-///   - $imeta
+// From https://github.com/tailhook/quick-error
+// Changes:
+//   - replace `impl Error` by `impl Item::description`
+//   - $imeta
 
 /// Because of the `#[macro_export(local_inner_macros)]` usage on `impl_error_chain_kind` that macro
 /// will only look inside this crate for macros to invoke. So using `stringify` or `write` from
@@ -21,22 +21,49 @@ macro_rules! write_internal {
 #[macro_export(local_inner_macros)]
 #[doc(hidden)]
 macro_rules! impl_error_chain_kind {
-    (   $(#[$meta:meta])*
-        pub enum $name:ident { $($chunks:tt)* }
+    (   $(#[$($meta:tt)*])*
+        $vis:vis enum $name:ident { $($chunks:tt)* }
     ) => {
-        impl_error_chain_kind!(SORT [pub enum $name $(#[$meta])* ]
+        impl_error_chain_kind!(DERIVES [$vis enum $name] [] []
+            $(#[$($meta)*])* { $($chunks)* });
+    };
+    // Collect the traits derived on the enum, which are also derived on the
+    // structs of `#[boxed(Struct)]` variants
+    (DERIVES $def:tt [$( $derives:tt )*] [$( $metas:tt )*]
+        #[derive($( $derive:path ),* $(,)*)] $( $tail:tt )*
+    ) => {
+        impl_error_chain_kind!(DERIVES $def [$( $derives )* $( $derive, )*]
+            [$( $metas )* #[derive($( $derive ),*)]] $( $tail )*);
+    };
+    (DERIVES $def:tt $derives:tt [$( $metas:tt )*]
+        #[$( $meta:tt )*] $( $tail:tt )*
+    ) => {
+        impl_error_chain_kind!(DERIVES $def $derives [$( $metas )* #[$( $meta )*]] $( $tail )*);
+    };
+    (DERIVES [$vis:vis enum $name:ident] $derives:tt [$( #[$meta:meta] )*]
+        { $($chunks:tt)* }
+    ) => {
+        impl_error_chain_kind!(SORT [$vis enum $name $derives $(#[$meta])* ]
+            items [] buf []
+            queue [ $($chunks)* ]);
+    };
+    // The enum is written by the user, see `#[derive(ErrorChain)]`.
+    (   DECLARED $(#[$meta:meta])*
+        $vis:vis enum $name:ident { $($chunks:tt)* }
+    ) => {
+        impl_error_chain_kind!(SORT [declared enum $name $(#[$meta])* ]
             items [] buf []
             queue [ $($chunks)* ]);
     };
     // Queue is empty, can do the work
-    (SORT [pub enum $name:ident $( #[$meta:meta] )*]
+    (SORT [$vis:vis enum $name:ident $derives:tt $( #[$meta:meta] )*]
         items [$($( #[$imeta:meta] )*
                   => $iitem:ident: $imode:tt [$( $ivar:ident: $ityp:ty ),*]
                                 {$( $ifuncs:tt )*} )* ]
         buf [ ]
         queue [ ]
     ) => {
-        impl_error_chain_kind!(ENUM_DEFINITION [pub enum $name $( #[$meta] )*]
+        impl_error_chain_kind!(ENUM_DEFINITION [$vis enum $name $derives $( #[$meta] )*]
             body []
             queue [$($( #[$imeta] )*
                       => $iitem: $imode [$( $ivar: $ityp ),*] )*]
@@ -44,10 +71,68 @@ macro_rules! impl_error_chain_kind {
         impl_error_chain_kind!(IMPLEMENTATIONS $name {$(
            $iitem: $imode [$(#[$imeta])*] [$( $ivar: $ityp ),*] {$( $ifuncs )*}
            )*});
+        impl_error_chain_kind!(DEBUG_IMPLEMENTATION $name {$(
+           $iitem: $imode [$(#[$imeta])*] [$( $ivar: $ityp ),*] {$( $ifuncs )*}
+           )*});
         $(
-            impl_error_chain_kind!(ERROR_CHECK $imode $($ifuncs)*);
+            impl_error_chain_kind!(ERROR_CHECK $iitem $imode $($ifuncs)*);
         )*
     };
+    // Queue is empty and the enum is already defined, with its `Debug`
+    // implementation.
+    (SORT [declared enum $name:ident $( #[$meta:meta] )*]
+        items [$($( #[$imeta:meta] )*
+                  => $iitem:ident: $imode:tt [$( $ivar:ident: $ityp:ty ),*]
+                                {$( $ifuncs:tt )*} )* ]
+        buf [ ]
+        queue [ ]
+    ) => {
+        impl_error_chain_kind!(IMPLEMENTATIONS $name {$(
+           $iitem: $imode [$(#[$imeta])*] [$( $ivar: $ityp ),*] {$( $ifuncs )*}
+           )*});
+        $(
+            impl_error_chain_kind!(ERROR_CHECK $iitem $imode $($ifuncs)*);
+        )*
+    };
+    // Store the fields of a `#[boxed(Struct)]` variant in a single `Box`
+    (SORT [$( $def:tt )*]
+        items $items:tt
+        buf $buf:tt
+        queue [ #[boxed($sname:ident)] $( #[$qmeta:meta] )*
+                $qitem:ident ($( $qvar:ident: $qtyp:ty ),+ $(,)?) $( $tail:tt )*]
+    ) => {
+        impl_error_chain_kind!(SORT [$( $def )*]
+            items $items
+            buf $buf
+            queue [ $( #[$qmeta] )*
+                    $qitem @boxed $sname [$( $qvar: $qtyp ),+] $( $tail )*]);
+    };
+    (SORT [$( $def:tt )*]
+        items $items:tt
+        buf $buf:tt
+        queue [ #[boxed($sname:ident)] $( #[$qmeta:meta] )*
+                $qitem:ident {$( $qvar:ident: $qtyp:ty ),+ $(,)?} $( $tail:tt )*]
+    ) => {
+        impl_error_chain_kind!(SORT [$( $def )*]
+            items $items
+            buf $buf
+            queue [ $( #[$qmeta] )*
+                    $qitem @boxed $sname [$( $qvar: $qtyp ),+] $( $tail )*]);
+    };
+    (SORT [$( $def:tt )*]
+        items $items:tt
+        buf $buf:tt
+        queue [ #[boxed($sname:ident)] $( #[$qmeta:meta] )* $qitem:ident $( $tail:tt )*]
+    ) => {
+        error_chain_compile_error!(BOXED_WITHOUT_FIELDS $qitem);
+    };
+    (SORT [$( $def:tt )*]
+        items $items:tt
+        buf $buf:tt
+        queue [ #[boxed] $( #[$qmeta:meta] )* $qitem:ident $( $tail:tt )*]
+    ) => {
+        error_chain_compile_error!(BOXED_WITHOUT_STRUCT $qitem);
+    };
     // Add meta to buffer
     (SORT [$( $def:tt )*]
         items [$($( #[$imeta:meta] )*
@@ -85,10 +170,8 @@ macro_rules! impl_error_chain_kind {
         queue [ #[$qmeta:meta] $( $tail:tt )*]
     ) => {
         impl_error_chain_kind!(SORT [$( $def )*]
-            enum [$(#[$bmeta])* => $bitem: $bmode $(( $($btyp),* ))*]
-            items [$($( #[$imeta:meta] )*
-                      => $iitem: $imode [$( $ivar:$ityp ),*] {$( $ifuncs )*} )*
-                     $bitem: $bmode [$( $bvar:$btyp ),*] {} ]
+            items [$( $(#[$imeta])* => $iitem: $imode [$( $ivar:$ityp ),*] {$( $ifuncs )*} )*
+                     $(#[$bmeta])* => $bitem: $bmode [$( $bvar:$btyp ),*] {} ]
             buf [ #[$qmeta] ]
             queue [$( $tail )*]);
     };
@@ -132,6 +215,19 @@ macro_rules! impl_error_chain_kind {
             buf [$( #[$bmeta] )* => $bitem: STRUCT [$( $qvar:$qtyp ),+] ]
             queue [$( $tail )*]);
     };
+    // Add boxed enum-variant
+    (SORT [$( $def:tt )*]
+        items [$($( #[$imeta:meta] )*
+                  => $iitem:ident: $imode:tt [$( $ivar:ident: $ityp:ty ),*]
+                                {$( $ifuncs:tt )*} )* ]
+        buf [$( #[$bmeta:meta] )* => $bitem:ident: UNIT [ ] ]
+        queue [@boxed $sname:ident [$( $qvar:ident: $qtyp:ty ),+] $( $tail:tt )*]
+    ) => {
+        impl_error_chain_kind!(SORT [$( $def )*]
+            items [$( $(#[$imeta])* => $iitem: $imode [$( $ivar:$ityp ),*] {$( $ifuncs )*} )*]
+            buf [$( #[$bmeta] )* => $bitem: (BOXED $sname) [$( $qvar:$qtyp ),+] ]
+            queue [$( $tail )*]);
+    };
     // Add braces and flush always on braces
     (SORT [$( $def:tt )*]
         items [$($( #[$imeta:meta] )*
@@ -177,14 +273,22 @@ macro_rules! impl_error_chain_kind {
             buf [ ]
             queue [ ]);
     };
+    // Anything else is not a variant.
+    (SORT [$( $def:tt )*]
+        items $items:tt
+        buf $buf:tt
+        queue [ $token:tt $( $tail:tt )* ]
+    ) => {
+        error_chain_compile_error!(INVALID_VARIANT $token);
+    };
     // Public enum (Queue Empty)
-    (ENUM_DEFINITION [pub enum $name:ident $( #[$meta:meta] )*]
+    (ENUM_DEFINITION [$vis:vis enum $name:ident $derives:tt $( #[$meta:meta] )*]
         body [$($( #[$imeta:meta] )*
             => $iitem:ident ($(($( $ttyp:ty ),+))*) {$({$( $svar:ident: $styp:ty ),*})*} )* ]
         queue [ ]
     ) => {
         $(#[$meta])*
-        pub enum $name {
+        $vis enum $name {
             $(
                 $(#[$imeta])*
                 $iitem $(($( $ttyp ),+))* $({$( $svar: $styp ),*})*,
@@ -233,6 +337,61 @@ macro_rules! impl_error_chain_kind {
             queue [ $($queue)* ]
         );
     };
+    // Boxed variant, whose fields are stored in a generated struct
+    (ENUM_DEFINITION [$vis:vis enum $name:ident [$( $derive:path, )*] $( #[$meta:meta] )*]
+        body [$($( #[$imeta:meta] )*
+            => $iitem:ident ($(($( $ttyp:ty ),+))*) {$({$( $svar:ident: $styp:ty ),*})*} )* ]
+        queue [$( #[$qmeta:meta] )*
+            => $qitem:ident: (BOXED $sname:ident) [$( $qvar:ident: $qtyp:ty ),+] $( $queue:tt )*]
+    ) => {
+        $( #[$qmeta] )*
+        #[derive(Debug $(, $derive )*)]
+        #[allow(missing_docs)]
+        $vis struct $sname {
+            $( pub $qvar: $qtyp ),+
+        }
+
+        $( #[$qmeta] )*
+        impl From<$sname> for $name {
+            fn from(fields: $sname) -> $name {
+                $name::$qitem($crate::__private::Box::new(fields))
+            }
+        }
+
+        impl_error_chain_kind!(ENUM_DEFINITION [$vis enum $name [$( $derive, )*] $( #[$meta] )*]
+            body [$($( #[$imeta] )* => $iitem ($(($( $ttyp ),+))*) {$({$( $svar: $styp ),*})*} )*
+                    $( #[$qmeta] )* => $qitem (($crate::__private::Box<$sname>)) {} ]
+            queue [ $($queue)* ]
+        );
+    };
+    (DEBUG_IMPLEMENTATION
+        $name:ident {$(
+            $item:ident: $imode:tt [$(#[$imeta:meta])*] [$( $var:ident: $typ:ty ),*] {$( $funcs:tt )*}
+        )*}
+    ) => {
+        #[allow(unknown_lints, unused, renamed_and_removed_lints)]
+        #[allow(unused_doc_comment, unused_doc_comments)]
+        impl $crate::__private::fmt::Debug for $name {
+            fn fmt(&self, fmt: &mut $crate::__private::fmt::Formatter)
+                -> $crate::__private::fmt::Result
+            {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_SENSITIVE_IMPL {$( $funcs )*});
+                            impl_error_chain_kind!(DEBUG_IMPL fmt $item: $imode [$( $var ),*])
+                        }
+                    )*
+
+                    _ => fmt.write_str("__Nonexhaustive"),
+                }
+            }
+        }
+    };
     (IMPLEMENTATIONS
         $name:ident {$(
             $item:ident: $imode:tt [$(#[$imeta:meta])*] [$( $var:ident: $typ:ty ),*] {$( $funcs:tt )*}
@@ -240,9 +399,9 @@ macro_rules! impl_error_chain_kind {
     ) => {
         #[allow(unknown_lints, unused, renamed_and_removed_lints)]
         #[allow(unused_doc_comment, unused_doc_comments)]
-        impl ::std::fmt::Display for $name {
-            fn fmt(&self, fmt: &mut ::std::fmt::Formatter)
-                -> ::std::fmt::Result
+        impl $crate::__private::fmt::Display for $name {
+            fn fmt(&self, fmt: &mut $crate::__private::fmt::Formatter)
+                -> $crate::__private::fmt::Result
             {
                 match *self {
                     $(
@@ -250,6 +409,8 @@ macro_rules! impl_error_chain_kind {
                         impl_error_chain_kind!(ITEM_PATTERN
                             $name $item: $imode [$( ref $var ),*]
                         ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_SENSITIVE_IMPL {$( $funcs )*});
                             let display_fn = impl_error_chain_kind!(FIND_DISPLAY_IMPL
                                 $name $item: $imode
                                 {$( $funcs )*});
@@ -262,7 +423,7 @@ macro_rules! impl_error_chain_kind {
                 }
             }
         }
-        #[allow(unknown_lints, unused, renamed_and_removed_lints)]
+        #[allow(unknown_lints, unused, renamed_and_removed_lints, bare_trait_objects)]
         #[allow(unused_doc_comment, unused_doc_comments)]
         impl $name {
             /// A string describing the error kind.
@@ -273,6 +434,7 @@ macro_rules! impl_error_chain_kind {
                         impl_error_chain_kind!(ITEM_PATTERN
                             $name $item: $imode [$( ref $var ),*]
                         ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
                             impl_error_chain_kind!(FIND_DESCRIPTION_IMPL
                                 $item: $imode self fmt [$( $var ),*]
                                 {$( $funcs )*})
@@ -282,24 +444,201 @@ macro_rules! impl_error_chain_kind {
                     _ => "",
                 }
             }
+
+            /// The names of all the variants, in declaration order.
+            pub const VARIANTS: &'static [&'static str] = &[
+                $(
+                    $(#[$imeta])*
+                    stringify_internal!($item),
+                )*
+            ];
+
+            /// The name of the variant, e.g. `"Msg"` for `ErrorKind::Msg(..)`.
+            pub fn variant_name(&self) -> &'static str {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => stringify_internal!($item),
+                    )*
+
+                    _ => "__Nonexhaustive",
+                }
+            }
+
+            /// The id given by the `message_id` clause of the variant, used
+            /// to translate its message.
+            pub fn message_id(&self) -> $crate::__private::Option<&'static str> {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_MESSAGE_ID_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => None,
+                }
+            }
+
+            /// The code given by the `code` clause of the variant.
+            pub fn code(&self) -> $crate::__private::Option<&'static str> {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_CODE_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => None,
+                }
+            }
+
+            /// The severity given by the `severity` clause of the variant,
+            /// `Error` by default.
+            pub fn severity(&self) -> $crate::classify::Severity {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_SEVERITY_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => $crate::classify::Severity::Error,
+                }
+            }
+
+            /// Whether the `retryable` clause of the variant marks it as
+            /// transient, so that the operation which failed may succeed if
+            /// retried.
+            pub fn is_retryable(&self) -> bool {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_RETRYABLE_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => false,
+                }
+            }
+
+            /// The hint given by the `help` clause of the variant, on how to
+            /// fix the error.
+            pub fn help(&self) -> $crate::__private::Option<$crate::__private::String> {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_SENSITIVE_IMPL {$( $funcs )*});
+                            impl_error_chain_kind!(FIND_HELP_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => None,
+                }
+            }
+
+            /// An entry per variant, in declaration order, with its name,
+            /// doc comment, description and code.
+            pub fn catalog() -> &'static [$crate::catalog::Entry] {
+                const CATALOG: &'static [$crate::catalog::Entry] = &[
+                    $(
+                        $(#[$imeta])*
+                        $crate::catalog::Entry::new(
+                            stringify_internal!($item),
+                            &[$( stringify_internal!($imeta) ),*],
+                            impl_error_chain_kind!(FIND_CATALOG_DESCRIPTION {$( $funcs )*}),
+                            impl_error_chain_kind!(FIND_CATALOG_CODE {$( $funcs )*}),
+                        ),
+                    )*
+                ];
+                CATALOG
+            }
+
+            /// Translates the message of the kind with `localizer`, or
+            /// returns `None` if the variant has no `message_id` or no
+            /// translation.
+            pub fn localize(&self, localizer: &$crate::localize::Localizer)
+                -> $crate::__private::Option<$crate::__private::String>
+            {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_SENSITIVE_IMPL {$( $funcs )*});
+                            impl_error_chain_kind!(FIND_LOCALIZE_IMPL localizer
+                                [$( $var ),*] {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => None,
+                }
+            }
         }
+
+        #[allow(unknown_lints, unused, renamed_and_removed_lints)]
+        #[allow(unused_doc_comment, unused_doc_comments)]
+        impl $crate::HttpStatus for $name {
+            fn http_status(&self) -> u16 {
+                match *self {
+                    $(
+                        $(#[$imeta])*
+                        impl_error_chain_kind!(ITEM_PATTERN
+                            $name $item: $imode [$( ref $var ),*]
+                        ) => {
+                            impl_error_chain_kind!(UNBOX $imode [$( $var ),*]);
+                            impl_error_chain_kind!(FIND_HTTP_STATUS_IMPL {$( $funcs )*})
+                        }
+                    )*
+
+                    _ => 500,
+                }
+            }
+        }
+
+        impl_error_chain_io!(KIND $name {$(
+            $item: $imode [$(#[$imeta])*] [$( $var: $typ ),*] {$( $funcs )*}
+        )*});
     };
     (FIND_DISPLAY_IMPL $name:ident $item:ident: $imode:tt
         { display($self_:tt) -> ($( $exprs:tt )*) $( $tail:tt )*}
     ) => {
-        |impl_error_chain_kind!(IDENT $self_): &$name, f: &mut ::std::fmt::Formatter| {
+        |impl_error_chain_kind!(IDENT $self_): &$name, f: &mut $crate::__private::fmt::Formatter| {
             write_internal!(f, $( $exprs )*)
         }
     };
     (FIND_DISPLAY_IMPL $name:ident $item:ident: $imode:tt
         { display($pattern:expr) $( $tail:tt )*}
     ) => {
-        |_, f: &mut ::std::fmt::Formatter| { write_internal!(f, $pattern) }
+        |_, f: &mut $crate::__private::fmt::Formatter| { write_internal!(f, $pattern) }
     };
     (FIND_DISPLAY_IMPL $name:ident $item:ident: $imode:tt
         { display($pattern:expr, $( $exprs:tt )*) $( $tail:tt )*}
     ) => {
-        |_, f: &mut ::std::fmt::Formatter| { write_internal!(f, $pattern, $( $exprs )*) }
+        |_, f: &mut $crate::__private::fmt::Formatter| { write_internal!(f, $pattern, $( $exprs )*) }
     };
     (FIND_DISPLAY_IMPL $name:ident $item:ident: $imode:tt
         { $t:tt $( $tail:tt )*}
@@ -311,7 +650,7 @@ macro_rules! impl_error_chain_kind {
     (FIND_DISPLAY_IMPL $name:ident $item:ident: $imode:tt
         { }
     ) => {
-        |self_: &$name, f: &mut ::std::fmt::Formatter| {
+        |self_: &$name, f: &mut $crate::__private::fmt::Formatter| {
             write_internal!(f, "{}", self_.description())
         }
     };
@@ -335,6 +674,152 @@ macro_rules! impl_error_chain_kind {
     ) => {
         stringify_internal!($item)
     };
+    (FIND_SENSITIVE_IMPL { sensitive($( $svar:ident ),* $(,)*) $( $tail:tt )*}) => {
+        $( let $svar = $crate::redaction::Sensitive($svar); )*
+    };
+    (FIND_SENSITIVE_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_SENSITIVE_IMPL {$( $tail )*})
+    };
+    (FIND_SENSITIVE_IMPL { }) => {};
+    (DEBUG_IMPL $fmt:ident $item:ident: UNIT []) => {
+        $fmt.write_str(stringify_internal!($item))
+    };
+    (DEBUG_IMPL $fmt:ident $item:ident: TUPLE [$( $var:ident ),*]) => {
+        $fmt.debug_tuple(stringify_internal!($item))
+            $( .field(&$var) )*
+            .finish()
+    };
+    (DEBUG_IMPL $fmt:ident $item:ident: (BOXED $sname:ident) [$( $var:ident ),*]) => {
+        impl_error_chain_kind!(DEBUG_IMPL $fmt $item: STRUCT [$( $var ),*])
+    };
+    (DEBUG_IMPL $fmt:ident $item:ident: STRUCT [$( $var:ident ),*]) => {
+        $fmt.debug_struct(stringify_internal!($item))
+            $( .field(stringify_internal!($var), &$var) )*
+            .finish()
+    };
+    (FIND_CODE_IMPL { code($code:expr) $( $tail:tt )*}) => {
+        Some($code)
+    };
+    (FIND_CODE_IMPL { linked($kind:expr) $( $tail:tt )*}) => {
+        $kind.code()
+    };
+    (FIND_CODE_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_CODE_IMPL {$( $tail )*})
+    };
+    (FIND_CODE_IMPL { }) => {
+        None
+    };
+    (FIND_CATALOG_CODE { code($code:expr) $( $tail:tt )*}) => {
+        Some($code)
+    };
+    (FIND_CATALOG_CODE { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_CATALOG_CODE {$( $tail )*})
+    };
+    (FIND_CATALOG_CODE { }) => {
+        None
+    };
+    (FIND_CATALOG_DESCRIPTION { description($description:literal) $( $tail:tt )*}) => {
+        Some($description)
+    };
+    // Computed from the fields, not known without a value.
+    (FIND_CATALOG_DESCRIPTION { description($description:expr) $( $tail:tt )*}) => {
+        None
+    };
+    (FIND_CATALOG_DESCRIPTION { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_CATALOG_DESCRIPTION {$( $tail )*})
+    };
+    (FIND_CATALOG_DESCRIPTION { }) => {
+        None
+    };
+    (FIND_MESSAGE_ID_IMPL { message_id($id:expr) $( $tail:tt )*}) => {
+        Some($id)
+    };
+    (FIND_MESSAGE_ID_IMPL { linked($kind:expr) $( $tail:tt )*}) => {
+        $kind.message_id()
+    };
+    (FIND_MESSAGE_ID_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_MESSAGE_ID_IMPL {$( $tail )*})
+    };
+    (FIND_MESSAGE_ID_IMPL { }) => {
+        None
+    };
+    (FIND_HELP_IMPL { help($( $args:tt )*) $( $tail:tt )*}) => {
+        Some($crate::__private::format!($( $args )*))
+    };
+    (FIND_HELP_IMPL { linked($kind:expr) $( $tail:tt )*}) => {
+        $kind.help()
+    };
+    (FIND_HELP_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_HELP_IMPL {$( $tail )*})
+    };
+    (FIND_HELP_IMPL { }) => {
+        None
+    };
+    (FIND_SEVERITY_IMPL { severity($level:ident) $( $tail:tt )*}) => {
+        $crate::classify::Severity::$level
+    };
+    (FIND_SEVERITY_IMPL { linked($kind:expr) $( $tail:tt )*}) => {
+        $kind.severity()
+    };
+    (FIND_SEVERITY_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_SEVERITY_IMPL {$( $tail )*})
+    };
+    (FIND_SEVERITY_IMPL { }) => {
+        $crate::classify::Severity::Error
+    };
+    (FIND_RETRYABLE_IMPL { retryable($expr:expr) $( $tail:tt )*}) => {
+        $expr
+    };
+    (FIND_RETRYABLE_IMPL { retryable $( $tail:tt )*}) => {
+        true
+    };
+    (FIND_RETRYABLE_IMPL { linked($kind:expr) $( $tail:tt )*}) => {
+        $kind.is_retryable()
+    };
+    (FIND_RETRYABLE_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_RETRYABLE_IMPL {$( $tail )*})
+    };
+    (FIND_RETRYABLE_IMPL { }) => {
+        false
+    };
+    (FIND_LOCALIZE_IMPL $localizer:ident [$( $var:ident ),*]
+        { message_id($id:expr) $( $tail:tt )*}
+    ) => {
+        $localizer.localize($id, &[
+            $( (stringify_internal!($var), &$var as &$crate::__private::fmt::Display) ),*
+        ])
+    };
+    (FIND_LOCALIZE_IMPL $localizer:ident [$( $var:ident ),*]
+        { linked($kind:expr) $( $tail:tt )*}
+    ) => {
+        $kind.localize($localizer)
+    };
+    (FIND_LOCALIZE_IMPL $localizer:ident [$( $var:ident ),*]
+        { $t:tt $( $tail:tt )*}
+    ) => {
+        impl_error_chain_kind!(FIND_LOCALIZE_IMPL $localizer [$( $var ),*] {$( $tail )*})
+    };
+    (FIND_LOCALIZE_IMPL $localizer:ident [$( $var:ident ),*] { }) => {
+        None
+    };
+    (FIND_HTTP_STATUS_IMPL { http_status($expr:expr) $( $tail:tt )*}) => {
+        $expr
+    };
+    (FIND_HTTP_STATUS_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_HTTP_STATUS_IMPL {$( $tail )*})
+    };
+    (FIND_HTTP_STATUS_IMPL { }) => {
+        500
+    };
+    (FIND_IO_KIND_IMPL { io_kind($expr:expr) $( $tail:tt )*}) => {
+        $expr
+    };
+    (FIND_IO_KIND_IMPL { $t:tt $( $tail:tt )*}) => {
+        impl_error_chain_kind!(FIND_IO_KIND_IMPL {$( $tail )*})
+    };
+    (FIND_IO_KIND_IMPL { }) => {
+        $crate::__private::io::ErrorKind::Other
+    };
     (ITEM_BODY $(#[$imeta:meta])* $item:ident: UNIT
     ) => { };
     (ITEM_BODY $(#[$imeta:meta])* $item:ident: TUPLE
@@ -361,23 +846,80 @@ macro_rules! impl_error_chain_kind {
     ) => {
         $name::$item {$( ref $var ),*}
     };
+    // The box is bound to the name of the first field until `UNBOX`.
+    (ITEM_PATTERN $name:ident $item:ident: (BOXED $sname:ident)
+        [ref $first:ident $(, ref $var:ident )*]
+    ) => {
+        $name::$item(ref $first)
+    };
+    // Binds the fields of a boxed variant by reference, as the other
+    // variants are by `ITEM_PATTERN`.
+    (UNBOX (BOXED $sname:ident) [$first:ident $(, $var:ident )*]) => {
+        let $sname { ref $first $(, ref $var )* } = **$first;
+    };
+    (UNBOX $imode:tt [$( $var:ident ),*]) => {};
     // This one should match all allowed sequences in "funcs" but not match
     // anything else.
     // This is to contrast FIND_* clauses which just find stuff they need and
     // skip everything else completely
-    (ERROR_CHECK $imode:tt display($self_:tt) -> ($( $exprs:tt )*) $( $tail:tt )*)
-    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $imode $($tail)*); };
-    (ERROR_CHECK $imode:tt display($pattern: expr) $( $tail:tt )*)
-    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $imode $($tail)*); };
-    (ERROR_CHECK $imode:tt display($pattern: expr, $( $exprs:tt )*) $( $tail:tt )*)
-    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $imode $($tail)*); };
-    (ERROR_CHECK $imode:tt description($expr:expr) $( $tail:tt )*)
-    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $imode $($tail)*); };
-    (ERROR_CHECK $imode:tt ) => {};
-    (ERROR_CHECK_COMMA $imode:tt , $( $tail:tt )*)
-    => { impl_error_chain_kind!(ERROR_CHECK $imode $($tail)*); };
-    (ERROR_CHECK_COMMA $imode:tt $( $tail:tt )*)
-    => { impl_error_chain_kind!(ERROR_CHECK $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt display($self_:tt) -> ($( $exprs:tt )*) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt display($pattern: expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt display($pattern: expr, $( $exprs:tt )*) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt description($expr:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt io_kind($expr:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt http_status($expr:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt message_id($id:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt code($code:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt linked($kind:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt sensitive($( $var:ident ),* $(,)*) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt help($pattern: expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt help($pattern: expr, $( $exprs:tt )*) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt severity($level:ident) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt retryable($expr:expr) $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt retryable $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK_COMMA $item $imode $($tail)*); };
+    (ERROR_CHECK $item:ident $imode:tt ) => {};
+    // Known clauses with unexpected arguments.
+    (ERROR_CHECK $item:ident $imode:tt display $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE display $item); };
+    (ERROR_CHECK $item:ident $imode:tt description $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE description $item); };
+    (ERROR_CHECK $item:ident $imode:tt io_kind $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE io_kind $item); };
+    (ERROR_CHECK $item:ident $imode:tt http_status $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE http_status $item); };
+    (ERROR_CHECK $item:ident $imode:tt message_id $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE message_id $item); };
+    (ERROR_CHECK $item:ident $imode:tt code $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE code $item); };
+    (ERROR_CHECK $item:ident $imode:tt sensitive $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE sensitive $item); };
+    (ERROR_CHECK $item:ident $imode:tt severity $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE severity $item); };
+    (ERROR_CHECK $item:ident $imode:tt help $( $tail:tt )*)
+    => { error_chain_compile_error!(INVALID_CLAUSE help $item); };
+    (ERROR_CHECK $item:ident $imode:tt $clause:ident $( $tail:tt )*)
+    => { error_chain_compile_error!(UNKNOWN_CLAUSE $clause $item); };
+    (ERROR_CHECK $item:ident $imode:tt $token:tt $( $tail:tt )*)
+    => { error_chain_compile_error!(UNEXPECTED_IN_VARIANT $token $item); };
+    (ERROR_CHECK_COMMA $item:ident $imode:tt , $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK $item $imode $($tail)*); };
+    (ERROR_CHECK_COMMA $item:ident $imode:tt $( $tail:tt )*)
+    => { impl_error_chain_kind!(ERROR_CHECK $item $imode $($tail)*); };
     // Utility functions
     (IDENT $ident:ident) => { $ident }
 }
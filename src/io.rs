@@ -0,0 +1,74 @@
+//! Conversions between error_chain errors and `std::io::Error`.
+//!
+//! With the `into_io` annotation, the error generated by `error_chain!`
+//! converts into a `std::io::Error`, which is useful when implementing
+//! `Read`, `Write` or other traits of `std::io`. The annotation is opt-in,
+//! as the conversion would conflict with one written by hand. The `io_kind`
+//! clause of a variant selects the `std::io::ErrorKind` of the result,
+//! `Other` by default. Linked kinds use their own `io_kind`, and foreign
+//! `std::io::Error`s keep their kind.
+//!
+//! `std::io::Error` needs its inner error to be `Sync`, which the generated
+//! error is not, so it is wrapped in a [`Chained`] keeping the messages of
+//! its chain. `get_ref().downcast_ref::<Error>()` therefore returns `None`,
+//! and `Error::from_io` is the way to recover the error:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use std::io;
+//!
+//! error_chain! {
+//!     into_io
+//!
+//!     errors {
+//!         MissingKey(key: String) {
+//!             display("missing key: {}", key)
+//!             io_kind(io::ErrorKind::NotFound)
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let e = Error::from(ErrorKind::MissingKey("a".into())).chain_err(|| "cannot open");
+//! let e = io::Error::from(e);
+//! assert_eq!(e.kind(), io::ErrorKind::Other);
+//! assert_eq!(e.to_string(), "cannot open");
+//!
+//! let e = Error::from_io(e).unwrap();
+//! assert_eq!(e.iter().count(), 2);
+//!
+//! let e = io::Error::from(Error::from(ErrorKind::MissingKey("a".into())));
+//! assert_eq!(e.kind(), io::ErrorKind::NotFound);
+//! # }
+//! ```
+//!
+//! [`Chained`]: ../struct.Chained.html
+
+use std::any::Any;
+use std::io;
+
+use {Chained, ChainedError};
+
+/// The kind of a foreign error, if it is a `std::io::Error`.
+#[doc(hidden)]
+#[allow(unknown_lints, bare_trait_objects)]
+pub fn foreign_io_kind<E: Any>(e: &E) -> io::ErrorKind {
+    match (e as &Any).downcast_ref::<io::Error>() {
+        Some(e) => e.kind(),
+        None => io::ErrorKind::Other,
+    }
+}
+
+/// Recovers an error wrapped by its conversion to `std::io::Error`.
+#[doc(hidden)]
+pub fn downcast<E: ChainedError>(e: io::Error) -> Result<E, io::Error> {
+    let wraps_e = e
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<Chained>())
+        .is_some_and(|e| e.is::<E>());
+    if !wraps_e {
+        return Err(e);
+    }
+    let chained = e.into_inner().unwrap().downcast::<Chained>().unwrap();
+    Ok(*chained.into_inner().downcast::<E>().unwrap())
+}
@@ -1,5 +1,6 @@
 #![deny(missing_docs)]
-#![doc(html_root_url = "https://docs.rs/error-chain/0.12.4")]
+#![doc(html_root_url = "https://docs.rs/error-chain/0.13.0")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! A library for consistent and reliable error handling
 //!
@@ -172,9 +173,142 @@
 //! # fn main() {}
 //! ```
 //!
+//! Instead of `skip_msg_variant`, the `msg_variant` annotation can rename
+//! the variant, as in `msg_variant Other;`, or change its payload to any
+//! type converted from a `String` and dereferencing to `str`, as in
+//! `msg_variant Message(Cow<'static, str>);`. With `msg_variant Msg
+//! without_from;` the variant is kept, but the `From` conversions from
+//! `String` and `&str` are not generated:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use std::borrow::Cow;
+//!
+//! error_chain! {
+//!     msg_variant Message(Cow<'static, str>) without_from;
+//! }
+//!
+//! # fn main() {
+//! let e = Error::from_kind(ErrorKind::Message(Cow::Borrowed("static")));
+//! assert_eq!(e.to_string(), "static");
+//! # }
+//! ```
+//!
 //! Each section, `types`, `links`, `foreign_links`, and `errors` may
 //! be omitted if it is empty.
 //!
+//! Each name of the `types` section can be preceded by outer attributes,
+//! which are added to the generated item. `ErrorKind` can for instance
+//! derive `PartialEq`, `Eq`, `Clone` and `Hash` when the fields of its
+//! variants, and the kinds of its `links`, implement them. `Debug` is
+//! always implemented, and `Error` does not support these derives since
+//! its internal state cannot be compared or cloned.
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! error_chain! {
+//!     types {
+//!         #[must_use] Error,
+//!         #[derive(PartialEq, Eq, Clone, Hash)] ErrorKind,
+//!         ResultExt, Result;
+//!     }
+//!
+//!     errors {
+//!         NotFound(path: String)
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let kind = ErrorKind::NotFound("a.txt".into());
+//! assert_eq!(kind.clone(), ErrorKind::NotFound("a.txt".into()));
+//! # }
+//! ```
+//!
+//! The generated types are public, unless a visibility is given before
+//! the name of `Error`, which then applies to all of them. This keeps the
+//! errors of an internal module out of the public API:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! mod parser {
+//!     error_chain! {
+//!         types {
+//!             pub(crate) Error, ErrorKind, ResultExt, Result;
+//!         }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Fields listed in the `sensitive` clause of a variant, such as
+//! `sensitive(token)`, are displayed as `[REDACTED]` while redaction is
+//! enabled, see the [`redaction`] module.
+//!
+//! The `message_id("file-not-found")` clause gives a variant an id that a
+//! [`Localizer`] implemented by the application can translate, see the
+//! [`localize`] module.
+//!
+//! The `help` clause gives a hint on how to fix the error, formatted like
+//! `display` from the fields of the variant. `Error::with_help` adds more
+//! hints to a single error. `display_chain` renders the hints of every
+//! error of the chain after its causes:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::ChainedError;
+//!
+//! error_chain! {
+//!     errors {
+//!         NotInitialized(tool: String) {
+//!             display("no repository found")
+//!             help("run `{} init` first", tool)
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! # #[cfg(feature = "std")]
+//! # let _guard = error_chain::suppress_backtraces();
+//! let e = Error::from(ErrorKind::NotInitialized("tool".into()))
+//!     .chain_err(|| "cannot commit")
+//!     .with_help("see `tool help commit`");
+//! assert_eq!(
+//!     e.display_chain().to_string(),
+//!     "Error: cannot commit\n\
+//!      Caused by: no repository found\n\
+//!      help: see `tool help commit`\n\
+//!      help: run `tool init` first\n"
+//! );
+//! # }
+//! ```
+//!
+//! `ErrorKind::catalog()` lists every variant with its doc comment,
+//! description and the code given by its `code("E0001")` clause, see the
+//! [`catalog`] module to publish them as Markdown or JSON.
+//!
+//! With the `derive` feature, `#[derive(ErrorChain)]` generates the same
+//! types from an `ErrorKind` enum written by hand, with `links` and
+//! `foreign_links` variants marked by attributes, see the
+//! [`error-chain-derive`] crate.
+//!
+//! By default the generated `Error` is a tuple struct holding the
+//! `ErrorKind` and the internal state side by side, which makes it
+//! fairly large. Adding the `thin` annotation stores both behind a single
+//! pointer instead, so that `Result<T, Error>` stays small on the hot path.
+//! `kind()`, `iter()`, `backtrace()` and the `From` conversions work the
+//! same, but the error can no longer be destructured as `Error(kind, _)`:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! error_chain! {
+//!     thin
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(::std::mem::size_of::<Error>(), ::std::mem::size_of::<usize>());
+//! # }
+//! ```
+//!
 //! This populates the module with a number of definitions,
 //! the most important of which are the [`Error`] type
 //! and the [`ErrorKind`] type. An example of generated code can be found in the
@@ -330,6 +464,32 @@
 //! types of all linked error chains. Linked errors do not introduce a new
 //! cause to the error chain.
 //!
+//! To accept errors from chains which are not known in advance, declare a
+//! single `opaque_links` variant. `Error::from_chained` then wraps an error
+//! of any `error_chain!` type, keeping its cause and backtrace, while its
+//! kind is stored as an [`OpaqueKind`] which can be downcast back:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! # mod other { error_chain! { errors { Timeout } } }
+//! error_chain! {
+//!     opaque_links {
+//!         Upstream;
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let e = Error::from_chained(other::Error::from_kind(other::ErrorKind::Timeout));
+//! match *e.kind() {
+//!     ErrorKind::Upstream(ref kind) => {
+//!         assert!(kind.is::<other::ErrorKind>());
+//!     }
+//!     _ => panic!(),
+//! }
+//! assert_eq!(e.kind().variant_path(), "Upstream::Timeout");
+//! # }
+//! ```
+//!
 //! ## Matching errors
 //!
 //! error-chain error variants are matched with simple patterns.
@@ -434,6 +594,24 @@
 //!
 //! The [`Error`] and [`ErrorKind`] types also allow programmatic access to these elements.
 //!
+//! The name of an [`ErrorKind`] variant is available as a string, which is
+//! handy for metrics labels or for generating a catalog of errors:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! # fn main() {
+//! error_chain! {
+//!     errors { Timeout }
+//! }
+//!
+//! assert_eq!(ErrorKind::VARIANTS, &["Msg", "Timeout"]);
+//! assert_eq!(ErrorKind::Timeout.variant_name(), "Timeout");
+//! # }
+//! ```
+//!
+//! For `links` variants, `variant_path` continues into the linked kind and
+//! returns e.g. `"Inner::Timeout"`.
+//!
 //! ## Foreign links
 //!
 //! Errors that do not conform to the same conventions as this library
@@ -454,6 +632,70 @@
 //! old error is discarded; there is no "cause" created from the
 //! original error.
 //!
+//! A foreign link can instead be classified by a function, or a closure,
+//! given after `=>`, which is called by the `From` conversion and returns
+//! the kind of the new error. Its attributes and `retryable` clause, if any,
+//! come before the `=>`:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use std::io;
+//!
+//! error_chain! {
+//!     foreign_links {
+//!         Io(io::Error) => map_io;
+//!     }
+//!
+//!     errors {
+//!         NotFound
+//!     }
+//! }
+//!
+//! fn map_io(e: io::Error) -> ErrorKind {
+//!     match e.kind() {
+//!         io::ErrorKind::NotFound => ErrorKind::NotFound,
+//!         _ => ErrorKind::Io(e),
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let e = Error::from(io::Error::from(io::ErrorKind::NotFound));
+//! assert_kind!(e, ErrorKind::NotFound);
+//! # }
+//! ```
+//!
+//! A large foreign error makes every `ErrorKind`, and so every `Result`,
+//! as large. Preceding its entry with `#[boxed]`, as in
+//! `#[boxed] Parse(ParseError);`, stores it in a `Box` instead. The `From`
+//! conversion boxes it, and the error is still found by
+//! [`assert_chain_contains!`] and forwards `source` as if stored inline.
+//! A variant of the `errors` section is boxed by naming the struct which
+//! holds its fields: `#[boxed(ParseDetails)] Parse(line: u32, msg: String)`
+//! generates `ParseDetails { line, msg }` with public fields, makes the
+//! variant `Parse(Box<ParseDetails>)`, and converts `ParseDetails` into the
+//! kind. Its clauses still see every field by reference, as for the other
+//! variants, and the struct derives `Debug` and the traits listed in the
+//! `#[derive(..)]` attributes of `ErrorKind`. `examples/size.rs` shows the
+//! savings.
+//!
+//! With the `into_io` annotation, errors also convert into `std::io::Error`,
+//! with the `std::io::ErrorKind` given by the `io_kind` clause of their
+//! variant, see the [`io`] module.
+//!
+//! The `http_status` clause maps variants to HTTP status codes, and the
+//! [`http`] module renders errors as RFC 7807 problem details.
+//!
+//! The `severity` and `retryable` clauses classify variants and
+//! `foreign_links` entries, and
+//! [`ChainedError::any_retryable`] tells whether an error of the chain is
+//! worth retrying, see the [`classify`] module. [`retry`] runs an
+//! operation again with exponential backoff while it fails with a retryable
+//! error, and records every attempt in the chain of its final error.
+//!
+//! With the `anyhow` feature, the [`anyhow`] module converts errors into
+//! `anyhow::Error`, and wraps an `anyhow::Error` so that it can be used as
+//! a foreign link or a cause.
+//!
 //! ## Backtraces
 //!
 //! If the `RUST_BACKTRACE` environment variable is set to anything
@@ -462,7 +704,14 @@
 //! and [`chain_err`] invocations of compatible types. To read the
 //! backtrace just call the [`backtrace`] method.
 //!
-//! Backtrace generation can be disabled by turning off the `backtrace` feature.
+//! Backtrace generation can be disabled by turning off the `backtrace` feature,
+//! or for the current thread with [`suppress_backtraces`], which is useful in
+//! tests. [`DisplayChain::normalized`] renders a chain without its backtrace
+//! and independently of the machine it runs on, for snapshot tests.
+//!
+//! When no backtrace is captured, creating an error with `from_kind` or
+//! converting an `ErrorKind` with `?` does not allocate, unless the kind
+//! itself does or the error is `thin`. Chaining an error boxes its cause.
 //!
 //! The Backtrace contains a Vec of [`BacktraceFrame`]s that can be operated
 //! on directly.  For example, to only see the files and line numbers of code
@@ -509,6 +758,69 @@
 //!
 //! The [`iter`] method returns an iterator over the chain of error boxes.
 //!
+//! ## Testing
+//!
+//! The [`testing`] module provides assertion macros which print the full
+//! chain of the error when they fail:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! # fn main() {
+//! error_chain! {
+//!     errors { Timeout }
+//! }
+//!
+//! let res: Result<()> = Err(ErrorKind::Timeout.into());
+//! assert_kind!(res, ErrorKind::Timeout);
+//! # }
+//! ```
+//!
+//! ## Metrics
+//!
+//! The [`metrics`] module lets an application install a global observer
+//! that is notified, with the error type, variant name and source location,
+//! every time an error is created. It costs a single atomic load when no
+//! observer is installed.
+//!
+//! ## Collecting errors
+//!
+//! When processing a batch of items it is often better to report every
+//! failure rather than stopping at the first one. The [`CollectErrors`]
+//! extension trait collects an iterator of `Result`s, and if anything
+//! failed returns a single error whose cause is a [`Failures`] holding
+//! every underlying error.
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::CollectErrors;
+//!
+//! error_chain! {}
+//!
+//! # fn main() {
+//! let res: Result<Vec<u8>> = vec!["1", "300", "x"]
+//!     .into_iter()
+//!     .map(|s| s.parse::<u8>())
+//!     .collect_errors_max(1, |f| format!("item {} is invalid", f.errors()[0].0));
+//! assert_eq!(res.unwrap_err().to_string(), "item 1 is invalid");
+//! # }
+//! ```
+//!
+//! ## `no_std`
+//!
+//! The crate only requires `core` and `alloc` when its default `std` feature
+//! is turned off, so the same error definitions can be shared between
+//! firmware and host tools:
+//!
+//! ```toml
+//! [dependencies]
+//! error-chain = { version = "0.13", default-features = false }
+//! ```
+//!
+//! Errors are then bound by `core::error::Error`, which requires Rust 1.81.
+//! `error_chain!`, `bail!`, `ensure!`, [`iter`] and [`ChainedError`] work
+//! as usual, while `quick_main!`, backtraces, [`suppress_backtraces`] and
+//! [`metrics::Counter`] need `std`. The `backtrace` feature enables `std`.
+//!
 //! [error-type]: https://github.com/DanielKeep/rust-error-type
 //! [quick-error]: https://github.com/tailhook/quick-error
 
@@ -516,6 +828,13 @@
 //! [`error_chain!`]: macro.error_chain.html
 //! [`bail!`]: macro.bail.html
 //! [`Backtrace`]: struct.Backtrace.html
+//! [`suppress_backtraces`]: fn.suppress_backtraces.html
+//! [`DisplayChain::normalized`]: struct.DisplayChain.html#method.normalized
+//! [`testing`]: testing/index.html
+//! [`metrics`]: metrics/index.html
+//! [`CollectErrors`]: trait.CollectErrors.html
+//! [`Failures`]: struct.Failures.html
+//! [`assert_chain_contains!`]: macro.assert_chain_contains.html
 
 //! [`Error`]: example_generated/struct.Error.html
 //! [`with_chain`]: example_generated/struct.Error.html#method.with_chain
@@ -523,6 +842,20 @@
 //! [`cause`]: example_generated/struct.Error.html#method.cause
 //! [`backtrace`]: example_generated/struct.Error.html#method.backtrace
 //! [`iter`]: example_generated/struct.Error.html#method.iter
+//! [`OpaqueKind`]: struct.OpaqueKind.html
+//! [`anyhow`]: anyhow/index.html
+//! [`io`]: io/index.html
+//! [`http`]: http/index.html
+//! [`classify`]: classify/index.html
+//! [`retry`]: retry/index.html
+//! [`ChainedError::any_retryable`]: trait.ChainedError.html#method.any_retryable
+//! [`redaction`]: redaction/index.html
+//! [`localize`]: localize/index.html
+//! [`catalog`]: catalog/index.html
+//! [`error-chain-derive`]: https://docs.rs/error-chain-derive
+//! [`Localizer`]: localize/trait.Localizer.html
+//! [`ChainedError`]: trait.ChainedError.html
+//! [`metrics::Counter`]: metrics/struct.Counter.html
 //! [`ErrorKind`]: example_generated/enum.ErrorKind.html
 //! [`description`]: example_generated/enum.ErrorKind.html#method.description
 //! [`Result`]: example_generated/type.Result.html
@@ -539,6 +872,16 @@
 //! [`map_err`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.map_err
 //! [`BacktraceFrame`]: https://docs.rs/backtrace/0.3.2/backtrace/struct.BacktraceFrame.html
 
+extern crate alloc;
+#[cfg(feature = "anyhow")]
+extern crate anyhow as anyhow_crate;
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(feature = "derive")]
+extern crate error_chain_derive;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use std::error;
 use std::fmt;
 use std::iter::Iterator;
@@ -547,42 +890,90 @@ use std::iter::Iterator;
 mod impl_error_chain_kind;
 #[macro_use]
 mod error_chain;
+#[cfg(feature = "std")]
 #[macro_use]
 mod quick_main;
+#[cfg(feature = "std")]
 pub use quick_main::ExitCode;
+#[cfg(feature = "anyhow")]
+pub mod anyhow;
 mod backtrace;
+pub mod catalog;
+#[cfg(feature = "std")]
+mod chained;
+pub mod classify;
+mod collect;
+mod erased;
+pub mod http;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod localize;
+mod normalize;
+mod opaque;
+pub mod redaction;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "std")]
+pub use chained::Chained;
+pub use collect::{CollectErrors, Failures};
+#[doc(hidden)]
+pub use erased::Registration;
+#[cfg(feature = "derive")]
+pub use error_chain_derive::ErrorChain;
+pub use http::HttpStatus;
+pub use opaque::{ChainedErrorKind, OpaqueKind};
+#[cfg(feature = "std")]
+pub use retry::retry;
+pub mod metrics;
+#[macro_use]
+pub mod testing;
 #[cfg(feature = "example_generated")]
 pub mod example_generated;
 pub use backtrace::Backtrace;
 #[doc(hidden)]
 pub use backtrace::InternalBacktrace;
+#[cfg(feature = "std")]
+pub use backtrace::{suppress_backtraces, SuppressBacktraces};
 
-#[derive(Debug)]
+/// Paths used by the generated code, which cannot rely on the calling crate
+/// having access to `std`.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+    pub use alloc::format;
+    pub use alloc::string::String;
+    pub use alloc::vec::Vec;
+    pub use std::error::Error;
+    pub use std::fmt;
+    #[cfg(feature = "std")]
+    pub use std::io;
+    pub use std::option::Option;
+    pub use std::result::Result;
+}
+
+/// A reference to an error in the chain, `'static` so that it can be
+/// downcast to its concrete type.
 #[allow(unknown_lints, bare_trait_objects)]
+type ErrorRef<'a> = &'a (error::Error + 'static);
+
+#[derive(Debug)]
 /// Iterator over the error chain using the `Error::cause()` method.
-pub struct Iter<'a>(Option<&'a error::Error>);
+pub struct Iter<'a>(Option<ErrorRef<'a>>);
 
 impl<'a> Iter<'a> {
     /// Returns a new iterator over the error chain using `Error::cause()`.
-    #[allow(unknown_lints, bare_trait_objects)]
-    pub fn new(err: Option<&'a error::Error>) -> Iter<'a> {
+    pub fn new(err: Option<ErrorRef<'a>>) -> Iter<'a> {
         Iter(err)
     }
 }
 
-#[allow(unknown_lints, bare_trait_objects)]
 impl<'a> Iterator for Iter<'a> {
-    type Item = &'a error::Error;
+    type Item = ErrorRef<'a>;
 
-    fn next<'b>(&'b mut self) -> Option<&'a error::Error> {
+    fn next<'b>(&'b mut self) -> Option<ErrorRef<'a>> {
         match self.0.take() {
             Some(e) => {
-                self.0 = match () {
-                    #[cfg(not(has_error_source))]
-                    () => e.cause(),
-                    #[cfg(has_error_source)]
-                    () => e.source(),
-                };
+                self.0 = e.source();
                 Some(e)
             }
             None => None,
@@ -592,6 +983,10 @@ impl<'a> Iterator for Iter<'a> {
 
 /// This trait is implemented on all the errors generated by the `error_chain`
 /// macro.
+///
+/// It is not meant to be implemented by hand: its hidden methods give
+/// access to the internal state of the error, and may change between
+/// releases.
 pub trait ChainedError: error::Error + Send + 'static {
     /// Associated kind type.
     type ErrorKind;
@@ -612,7 +1007,7 @@ pub trait ChainedError: error::Error + Send + 'static {
     fn kind(&self) -> &Self::ErrorKind;
 
     /// Iterates over the error chain.
-    fn iter(&self) -> Iter;
+    fn iter(&self) -> Iter<'_>;
 
     /// Returns the backtrace associated with this error.
     fn backtrace(&self) -> Option<&Backtrace>;
@@ -622,7 +1017,25 @@ pub trait ChainedError: error::Error + Send + 'static {
     ///
     /// The full cause chain and backtrace, if present, will be printed.
     fn display_chain<'a>(&'a self) -> DisplayChain<'a, Self> {
-        DisplayChain(self)
+        DisplayChain {
+            error: self,
+            normalized: false,
+            #[cfg(feature = "std")]
+            redaction: None,
+            localizer: None,
+        }
+    }
+
+    /// Returns `true` if an error of the chain is retryable: an error
+    /// generated by any `error_chain!` whose kind is retryable, or a
+    /// `std::io::Error` retryable according to `classify::is_transient_io`.
+    /// See the
+    /// [`classify`](classify/index.html) module.
+    fn any_retryable(&self) -> bool
+    where
+        Self: Sized,
+    {
+        classify::any_retryable(self)
     }
 
     /// Extends the error chain with a new entry.
@@ -637,6 +1050,16 @@ pub trait ChainedError: error::Error + Send + 'static {
     where
         Self: Sized;
 
+    /// Returns the state of the error.
+    #[doc(hidden)]
+    fn state(&self) -> &State;
+
+    /// Splits the error into its parts.
+    #[doc(hidden)]
+    fn into_parts(self) -> (Self::ErrorKind, State)
+    where
+        Self: Sized;
+
     /// Returns the first known backtrace, either from its State or from one
     /// of the errors from `foreign_links`.
     #[doc(hidden)]
@@ -644,24 +1067,170 @@ pub trait ChainedError: error::Error + Send + 'static {
     fn extract_backtrace(e: &(error::Error + Send + 'static)) -> Option<InternalBacktrace>
     where
         Self: Sized;
+
+    /// Returns a type-erased view of `e` if it is this error type or one of
+    /// the error types from `links`.
+    #[doc(hidden)]
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn extract_erased<'a>(e: &'a (error::Error + 'static)) -> Option<&'a ErasedChainedError>
+    where
+        Self: Sized,
+    {
+        let _ = e;
+        None
+    }
+}
+
+/// Object-safe view of an error generated by `error_chain!`, used to inspect
+/// the errors of a chain without knowing their types.
+#[doc(hidden)]
+pub trait ErasedChainedError {
+    /// Returns the error held by the kind, if it is a `foreign_links` variant.
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn foreign_error(&self) -> Option<&(error::Error + Send + 'static)> {
+        None
+    }
+
+    /// Translates the message of the kind with `localizer`.
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn localize(&self, localizer: &localize::Localizer) -> Option<alloc::string::String> {
+        let _ = localizer;
+        None
+    }
+
+    /// The severity of the kind.
+    fn severity(&self) -> classify::Severity {
+        classify::Severity::Error
+    }
+
+    /// Whether the kind is retryable.
+    fn is_retryable(&self) -> bool {
+        false
+    }
+
+    /// The hint of the kind, followed by the ones added with `with_help`.
+    fn help(&self) -> Vec<alloc::string::String> {
+        Vec::new()
+    }
 }
 
 /// A struct which formats an error for output.
-#[derive(Debug)]
-pub struct DisplayChain<'a, T: 'a + ?Sized>(&'a T);
+pub struct DisplayChain<'a, T: 'a + ?Sized> {
+    error: &'a T,
+    normalized: bool,
+    #[cfg(feature = "std")]
+    redaction: Option<bool>,
+    #[allow(unknown_lints, bare_trait_objects)]
+    localizer: Option<&'a (localize::Localizer + 'a)>,
+}
+
+impl<'a, T: 'a + ?Sized + fmt::Debug> fmt::Debug for DisplayChain<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DisplayChain")
+            .field("error", &self.error)
+            .field("normalized", &self.normalized)
+            .field("localized", &self.localizer.is_some())
+            .finish()
+    }
+}
+
+impl<'a, T: ?Sized> DisplayChain<'a, T> {
+    /// Renders the chain deterministically, for snapshot tests.
+    ///
+    /// The backtrace is left out, absolute paths are replaced by their file
+    /// name prefixed with `[..]/`, and hexadecimal addresses such as
+    /// `0x7fa9f684fc94` are replaced by `[ADDR]`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate error_chain;
+    /// use error_chain::ChainedError;
+    ///
+    /// error_chain! {}
+    ///
+    /// # fn main() {
+    /// let e = Error::from("can't read /home/alice/config.toml at 0x7ffd5a1c");
+    /// assert_eq!(
+    ///     e.display_chain().normalized().to_string(),
+    ///     "Error: can't read [..]/config.toml at [ADDR]\n"
+    /// );
+    /// # }
+    /// ```
+    pub fn normalized(mut self) -> Self {
+        self.normalized = true;
+        self
+    }
+
+    /// Renders `sensitive` fields as `[REDACTED]`, whether or not redaction
+    /// is enabled globally. See the [`redaction`](redaction/index.html)
+    /// module.
+    #[cfg(feature = "std")]
+    pub fn redacted(mut self) -> Self {
+        self.redaction = Some(true);
+        self
+    }
+
+    /// Renders `sensitive` fields as they are, whether or not redaction is
+    /// enabled globally. This is meant for local debugging.
+    #[cfg(feature = "std")]
+    pub fn internal(mut self) -> Self {
+        self.redaction = Some(false);
+        self
+    }
+
+    /// Renders the messages of variants with a `message_id` through
+    /// `localizer`, falling back to their `display` output when there is no
+    /// translation. See the [`localize`](localize/index.html) module.
+    #[allow(unknown_lints, bare_trait_objects)]
+    pub fn localized(mut self, localizer: &'a (localize::Localizer + 'a)) -> Self {
+        self.localizer = Some(localizer);
+        self
+    }
+}
 
 impl<'a, T> fmt::Display for DisplayChain<'a, T>
 where
     T: ChainedError,
 {
+    #[cfg(feature = "std")]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(fmt, "Error: {}", self.0)?;
+        redaction::with_override(self.redaction, || self.fmt_chain(fmt))
+    }
 
-        for e in self.0.iter().skip(1) {
-            writeln!(fmt, "Caused by: {}", e)?;
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_chain(fmt)
+    }
+}
+
+impl<'a, T> DisplayChain<'a, T>
+where
+    T: ChainedError,
+{
+    fn fmt_chain(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, e) in self.error.iter().enumerate() {
+            let prefix = if i == 0 { "Error" } else { "Caused by" };
+            let localized = self.localizer.and_then(|l| localize_error::<T>(e, l));
+            match (localized, self.normalized) {
+                (Some(s), true) => writeln!(fmt, "{}: {}", prefix, normalize::Normalized(s))?,
+                (Some(s), false) => writeln!(fmt, "{}: {}", prefix, s)?,
+                (None, true) => writeln!(fmt, "{}: {}", prefix, normalize::Normalized(e))?,
+                (None, false) => writeln!(fmt, "{}: {}", prefix, e)?,
+            }
+        }
+
+        for help in help_of_chain::<T>(self.error) {
+            if self.normalized {
+                writeln!(fmt, "help: {}", normalize::Normalized(help))?;
+            } else {
+                writeln!(fmt, "help: {}", help)?;
+            }
+        }
+
+        if self.normalized {
+            return Ok(());
         }
 
-        if let Some(backtrace) = ChainedError::backtrace(self.0) {
+        if let Some(backtrace) = ChainedError::backtrace(self.error) {
             writeln!(fmt, "{:?}", backtrace)?;
         }
 
@@ -669,7 +1238,27 @@ where
     }
 }
 
+/// Translates `e` if it is an error generated by `error_chain!`.
+#[allow(unknown_lints, bare_trait_objects)]
+fn localize_error<T: ChainedError>(
+    e: ErrorRef,
+    localizer: &localize::Localizer,
+) -> Option<alloc::string::String> {
+    erased::extract::<T>(e).and_then(|e| e.localize(localizer))
+}
+
+/// The hints of every error of the chain of `e` generated by `error_chain!`.
+fn help_of_chain<T: ChainedError>(e: &T) -> Vec<alloc::string::String> {
+    e.iter()
+        .filter_map(erased::extract::<T>)
+        .flat_map(|e| e.help())
+        .collect()
+}
+
 /// Common state between errors.
+///
+/// The default state, used by errors without a cause, does not allocate
+/// unless a backtrace is captured.
 #[derive(Debug)]
 #[doc(hidden)]
 #[allow(unknown_lints, bare_trait_objects)]
@@ -678,13 +1267,17 @@ pub struct State {
     pub next_error: Option<Box<error::Error + Send>>,
     /// Backtrace for the current error.
     pub backtrace: InternalBacktrace,
+    /// Hints added with `with_help`, boxed as most errors have none.
+    pub help: Option<Box<Vec<alloc::string::String>>>,
 }
 
 impl Default for State {
+    #[inline]
     fn default() -> State {
         State {
             next_error: None,
             backtrace: InternalBacktrace::new(),
+            help: None,
         }
     }
 }
@@ -696,7 +1289,8 @@ impl State {
         let backtrace = CE::extract_backtrace(&*e).unwrap_or_else(InternalBacktrace::new);
         State {
             next_error: Some(e),
-            backtrace: backtrace,
+            backtrace,
+            help: None,
         }
     }
 
@@ -778,7 +1372,7 @@ macro_rules! bail {
         return Err($e.into());
     };
     ($fmt:expr, $($arg:tt)+) => {
-        return Err(format!($fmt, $($arg)+).into());
+        return Err($crate::__private::format!($fmt, $($arg)+).into());
     };
 }
 
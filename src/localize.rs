@@ -0,0 +1,79 @@
+//! Translating error messages, see `DisplayChain::localized`.
+//!
+//! Variants given a `message_id` clause can be rendered through a
+//! [`Localizer`] implemented by the application, which receives the message
+//! id and the named fields of the variant. Errors without a message id, or
+//! for which the localizer has no translation, fall back to their
+//! `display` output.
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use std::fmt;
+//! use error_chain::ChainedError;
+//! use error_chain::localize::Localizer;
+//!
+//! error_chain! {
+//!     errors {
+//!         FileNotFound(path: String) {
+//!             display("file not found: {}", path)
+//!             message_id("file-not-found")
+//!         }
+//!     }
+//! }
+//!
+//! struct French;
+//!
+//! impl Localizer for French {
+//!     fn localize(&self, id: &str, args: &[(&str, &fmt::Display)]) -> Option<String> {
+//!         match id {
+//!             "file-not-found" => Some(format!("fichier introuvable : {}", args[0].1)),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! # #[cfg(feature = "std")]
+//! # let _guard = error_chain::suppress_backtraces();
+//! let e = Error::with_chain(
+//!     Error::from(ErrorKind::FileNotFound("a.txt".into())),
+//!     "cannot open the project",
+//! );
+//! assert_eq!(e.kind().message_id(), None);
+//! assert_eq!(
+//!     e.display_chain().localized(&French).to_string(),
+//!     "Error: cannot open the project\nCaused by: fichier introuvable : a.txt\n"
+//! );
+//! # }
+//! ```
+//!
+//! The fields of the variant must implement `Display`. Only the errors of
+//! the chain of the same `error_chain!` type, or of one of its `links`, are
+//! translated.
+//!
+//! [`Localizer`]: trait.Localizer.html
+
+use alloc::string::String;
+use std::fmt;
+
+/// Translates the messages of errors, see the [module
+/// documentation](index.html).
+pub trait Localizer {
+    /// Returns the translation of the message `id`, or `None` if there is
+    /// none.
+    ///
+    /// `args` holds the name and value of every field of the variant, in
+    /// declaration order.
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn localize(&self, id: &str, args: &[(&str, &fmt::Display)]) -> Option<String>;
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+impl<F> Localizer for F
+where
+    F: Fn(&str, &[(&str, &fmt::Display)]) -> Option<String>,
+{
+    fn localize(&self, id: &str, args: &[(&str, &fmt::Display)]) -> Option<String> {
+        self(id, args)
+    }
+}
@@ -0,0 +1,205 @@
+//! Observing the construction of errors.
+//!
+//! An [`Observer`] installed with [`set_observer`] is notified every time an
+//! error generated by [`error_chain!`] is created, whether through
+//! `from_kind`, `with_chain`, `chain_err` or a `From` conversion. This makes
+//! it possible to count errors by kind without touching every call site.
+//! Like a `OnceLock`, the observer is installed once for the whole program.
+//!
+//! When no observer is installed the cost of the hook is a single atomic
+//! load.
+//!
+#![cfg_attr(feature = "std", doc = "```")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::metrics::{self, Counter};
+//!
+//! error_chain! {
+//!     errors { Timeout }
+//! }
+//!
+//! static COUNTER: Counter = Counter::new();
+//!
+//! # fn main() {
+//! metrics::set_observer(&COUNTER).unwrap();
+//! let _ = Error::from_kind(ErrorKind::Timeout);
+//! assert_eq!(COUNTER.count(std::any::type_name::<Error>(), "Timeout"), 1);
+//! # }
+//! ```
+//!
+//! [`Observer`]: trait.Observer.html
+//! [`set_observer`]: fn.set_observer.html
+//! [`error_chain!`]: ../macro.error_chain.html
+
+use alloc::boxed::Box;
+use std::any;
+use std::fmt;
+use std::panic::Location;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+/// Describes the construction of an error.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorEvent {
+    type_name: &'static str,
+    variant_name: &'static str,
+    location: &'static Location<'static>,
+}
+
+impl ErrorEvent {
+    /// The full path of the error type, as returned by
+    /// `std::any::type_name`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The name of the `ErrorKind` variant.
+    pub fn variant_name(&self) -> &'static str {
+        self.variant_name
+    }
+
+    /// Where the error was created.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl fmt::Display for ErrorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}::{} at {}",
+            self.type_name, self.variant_name, self.location
+        )
+    }
+}
+
+/// Receives an event for every error created.
+pub trait Observer: Send + Sync {
+    /// Called when an error is created.
+    fn on_error(&self, event: &ErrorEvent);
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+static OBSERVER: AtomicPtr<&'static Observer> = AtomicPtr::new(ptr::null_mut());
+
+/// Returned by [`set_observer`] when an observer is already installed.
+///
+/// [`set_observer`]: fn.set_observer.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlreadyInstalled;
+
+impl fmt::Display for AlreadyInstalled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an observer is already installed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AlreadyInstalled {}
+
+/// Installs the global observer.
+///
+/// The observer can only be installed once, so that it is read without
+/// locking and its registration is never freed while in use.
+#[allow(unknown_lints, bare_trait_objects)]
+pub fn set_observer(observer: &'static Observer) -> Result<(), AlreadyInstalled> {
+    if !OBSERVER.load(Ordering::Acquire).is_null() {
+        return Err(AlreadyInstalled);
+    }
+    let registration = Box::into_raw(Box::new(observer));
+    match OBSERVER.compare_exchange(
+        ptr::null_mut(),
+        registration,
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    ) {
+        Ok(_) => Ok(()),
+        Err(_) => {
+            // Another thread won the race, this registration was never shared.
+            drop(unsafe { Box::from_raw(registration) });
+            Err(AlreadyInstalled)
+        }
+    }
+}
+
+/// Notifies the observer, if any, of the creation of an `E`.
+///
+/// The variant name is only computed when an observer is installed.
+#[doc(hidden)]
+#[inline]
+#[track_caller]
+pub fn notify<E: ?Sized, F>(variant_name: F)
+where
+    F: FnOnce() -> &'static str,
+{
+    let observer = OBSERVER.load(Ordering::Acquire);
+    if observer.is_null() {
+        return;
+    }
+    let event = ErrorEvent {
+        type_name: any::type_name::<E>(),
+        variant_name: variant_name(),
+        location: Location::caller(),
+    };
+    // The registration is never freed once installed, see `set_observer`.
+    unsafe { (*observer).on_error(&event) }
+}
+
+/// An observer counting errors by type and variant, mostly useful in tests.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct Counter {
+    counts: Mutex<Vec<(&'static str, &'static str, usize)>>,
+}
+
+#[cfg(feature = "std")]
+impl Counter {
+    /// Creates a counter with every count at zero.
+    pub const fn new() -> Counter {
+        Counter {
+            counts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The number of errors of the given type and variant seen so far.
+    pub fn count(&self, type_name: &str, variant_name: &str) -> usize {
+        self.counts
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|&&(t, v, _)| t == type_name && v == variant_name)
+            .map_or(0, |&(_, _, n)| n)
+    }
+
+    /// The number of errors seen so far.
+    pub fn total(&self) -> usize {
+        self.counts.lock().unwrap().iter().map(|&(_, _, n)| n).sum()
+    }
+
+    /// Every `(type name, variant name, count)` seen so far.
+    pub fn snapshot(&self) -> Vec<(&'static str, &'static str, usize)> {
+        self.counts.lock().unwrap().clone()
+    }
+
+    /// Sets every count back to zero.
+    pub fn reset(&self) {
+        self.counts.lock().unwrap().clear();
+    }
+}
+
+#[cfg(feature = "std")]
+impl Observer for Counter {
+    fn on_error(&self, event: &ErrorEvent) {
+        let mut counts = self.counts.lock().unwrap();
+        let position = counts
+            .iter()
+            .position(|&(t, v, _)| t == event.type_name && v == event.variant_name);
+        match position {
+            Some(i) => counts[i].2 += 1,
+            None => counts.push((event.type_name, event.variant_name, 1)),
+        }
+    }
+}
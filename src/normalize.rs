@@ -0,0 +1,93 @@
+//! Machine-independent rendering of error messages, see
+//! `DisplayChain::normalized`.
+
+use alloc::string::{String, ToString};
+use std::fmt::{self, Write};
+
+/// Displays a value with its absolute paths and addresses normalized.
+pub struct Normalized<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for Normalized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&normalize(&self.0.to_string()))
+    }
+}
+
+/// Replaces absolute paths by `[..]/` followed by their file name, and
+/// hexadecimal addresses by `[ADDR]`.
+pub fn normalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    let mut at_token_start = true;
+
+    while let Some(c) = rest.chars().next() {
+        if at_token_start {
+            let len = absolute_path_len(rest);
+            if len > 0 {
+                let path = &rest[..len];
+                let name = path.rsplit(['/', '\\']).next().unwrap_or("");
+                out.push_str("[..]/");
+                out.push_str(name);
+                rest = &rest[len..];
+                at_token_start = false;
+                continue;
+            }
+            let len = address_len(rest);
+            if len > 0 {
+                out.push_str("[ADDR]");
+                rest = &rest[len..];
+                at_token_start = false;
+                continue;
+            }
+        }
+        out.write_char(c).unwrap();
+        rest = &rest[c.len_utf8()..];
+        at_token_start = is_separator(c);
+    }
+
+    out
+}
+
+/// Characters after which a path or an address may start.
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || "'\"`([{<=,;".contains(c)
+}
+
+/// Characters ending a path.
+fn ends_path(c: char) -> bool {
+    c.is_whitespace() || "'\"`)]}>,;".contains(c)
+}
+
+/// Length of the absolute path at the start of `s`, or 0.
+fn absolute_path_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let is_unix = bytes.len() > 1 && bytes[0] == b'/' && !ends_path(bytes[1] as char);
+    let is_windows = bytes.len() > 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/');
+    if !is_unix && !is_windows {
+        return 0;
+    }
+    let len = s.find(ends_path).unwrap_or(s.len());
+    // Keep the `:line:column` suffix of source locations.
+    match s[..len].rfind(['/', '\\']) {
+        Some(sep) => s[sep..len].find(':').map_or(len, |colon| sep + colon),
+        None => len,
+    }
+}
+
+/// Length of the hexadecimal address at the start of `s`, or 0.
+fn address_len(s: &str) -> usize {
+    if !s.starts_with("0x") {
+        return 0;
+    }
+    let digits = s[2..]
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(s.len() - 2);
+    if digits == 0 {
+        0
+    } else {
+        2 + digits
+    }
+}
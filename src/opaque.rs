@@ -0,0 +1,179 @@
+//! Wrapping the kind of an error of any `error_chain!` type, see
+//! `opaque_links`.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use std::any::Any;
+use std::fmt;
+
+use classify::Severity;
+use localize::Localizer;
+use HttpStatus;
+
+/// Implemented by every `ErrorKind` generated by `error_chain!`.
+pub trait ChainedErrorKind: fmt::Debug + fmt::Display + HttpStatus {
+    /// A string describing the error kind.
+    fn description(&self) -> &str;
+
+    /// The name of the variant, e.g. `"Msg"`.
+    fn variant_name(&self) -> &'static str;
+
+    /// The name of the variant, continuing into linked kinds, e.g.
+    /// `"Inner::Timeout"`.
+    fn variant_path(&self) -> String {
+        String::from(self.variant_name())
+    }
+
+    /// The id given by the `message_id` clause of the variant.
+    fn message_id(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The code given by the `code` clause of the variant.
+    fn code(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Translates the message of the kind with `localizer`.
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn localize(&self, localizer: &Localizer) -> Option<String> {
+        let _ = localizer;
+        None
+    }
+
+    /// The severity given by the `severity` clause of the variant.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Whether the variant is retryable, see the `retryable` clause.
+    fn is_retryable(&self) -> bool {
+        false
+    }
+
+    /// The hint given by the `help` clause of the variant.
+    fn help(&self) -> Option<String> {
+        None
+    }
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+trait AnyKind: ChainedErrorKind + Send {
+    fn as_any(&self) -> &Any;
+    fn into_any(self: Box<Self>) -> Box<Any + Send>;
+}
+
+#[allow(unknown_lints, bare_trait_objects)]
+impl<K: ChainedErrorKind + Send + 'static> AnyKind for K {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<Any + Send> {
+        self
+    }
+}
+
+/// The kind of an error of another `error_chain!` type, held by the
+/// `opaque_links` variant.
+///
+/// The original kind can be recovered with [`downcast_ref`] or
+/// [`downcast`].
+///
+/// [`downcast_ref`]: #method.downcast_ref
+/// [`downcast`]: #method.downcast
+#[allow(unknown_lints, bare_trait_objects)]
+pub struct OpaqueKind(Box<AnyKind>);
+
+impl OpaqueKind {
+    /// Wraps a kind.
+    pub fn new<K: ChainedErrorKind + Send + 'static>(kind: K) -> OpaqueKind {
+        OpaqueKind(Box::new(kind))
+    }
+
+    /// Returns `true` if the wrapped kind is a `K`.
+    pub fn is<K: 'static>(&self) -> bool {
+        self.0.as_any().is::<K>()
+    }
+
+    /// Returns the wrapped kind if it is a `K`.
+    pub fn downcast_ref<K: 'static>(&self) -> Option<&K> {
+        self.0.as_any().downcast_ref()
+    }
+
+    /// Returns the wrapped kind if it is a `K`, or `self` otherwise.
+    pub fn downcast<K: 'static>(self) -> Result<K, OpaqueKind> {
+        if self.is::<K>() {
+            Ok(*self.0.into_any().downcast().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// A string describing the wrapped kind.
+    pub fn description(&self) -> &str {
+        self.0.description()
+    }
+
+    /// The name of the variant of the wrapped kind.
+    pub fn variant_name(&self) -> &'static str {
+        self.0.variant_name()
+    }
+
+    /// The path of the variant of the wrapped kind, see
+    /// [`ChainedErrorKind::variant_path`].
+    ///
+    /// [`ChainedErrorKind::variant_path`]: trait.ChainedErrorKind.html#tymethod.variant_path
+    pub fn variant_path(&self) -> String {
+        self.0.variant_path()
+    }
+
+    /// The message id of the wrapped kind.
+    pub fn message_id(&self) -> Option<&'static str> {
+        self.0.message_id()
+    }
+
+    /// The code of the wrapped kind.
+    pub fn code(&self) -> Option<&'static str> {
+        self.0.code()
+    }
+
+    /// Translates the message of the wrapped kind with `localizer`.
+    #[allow(unknown_lints, bare_trait_objects)]
+    pub fn localize(&self, localizer: &Localizer) -> Option<String> {
+        self.0.localize(localizer)
+    }
+
+    /// The severity of the wrapped kind.
+    pub fn severity(&self) -> Severity {
+        self.0.severity()
+    }
+
+    /// Whether the wrapped kind is retryable.
+    pub fn is_retryable(&self) -> bool {
+        self.0.is_retryable()
+    }
+
+    /// The hint of the wrapped kind.
+    pub fn help(&self) -> Option<String> {
+        self.0.help()
+    }
+}
+
+impl HttpStatus for OpaqueKind {
+    fn http_status(&self) -> u16 {
+        self.0.http_status()
+    }
+}
+
+impl fmt::Debug for OpaqueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for OpaqueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
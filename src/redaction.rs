@@ -0,0 +1,134 @@
+//! Hiding sensitive data when displaying errors.
+//!
+//! The fields listed in the `sensitive` clause of a variant are rendered as
+//! `[REDACTED]` by the `Display` and `Debug` implementations of the kind
+//! while redaction is enabled, either globally with [`set_enabled`] or for
+//! one rendering with [`DisplayChain::redacted`]. [`DisplayChain::internal`]
+//! shows them regardless, for local debugging.
+//!
+#![cfg_attr(feature = "std", doc = "```")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
+//! # #[macro_use] extern crate error_chain;
+//! use error_chain::ChainedError;
+//!
+//! error_chain! {
+//!     errors {
+//!         Auth(user: String, token: String) {
+//!             display("failed to auth {} with token {}", user, token)
+//!             sensitive(token)
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! # let _guard = error_chain::suppress_backtraces();
+//! let e = Error::from(ErrorKind::Auth("alice".into(), "hunter2".into()));
+//! assert_eq!(
+//!     e.display_chain().redacted().to_string(),
+//!     "Error: failed to auth alice with token [REDACTED]\n"
+//! );
+//!
+//! error_chain::redaction::set_enabled(true);
+//! assert_eq!(e.to_string(), "failed to auth alice with token [REDACTED]");
+//! assert_eq!(format!("{:?}", e.kind()), r#"Auth("alice", [REDACTED])"#);
+//! assert_eq!(
+//!     e.display_chain().internal().to_string(),
+//!     "Error: failed to auth alice with token hunter2\n"
+//! );
+//! # error_chain::redaction::set_enabled(false);
+//! # }
+//! ```
+//!
+//! Only the fields of variants are redacted: messages built with `format!`
+//! and given to `chain_err` are displayed as they are, and so are the
+//! fields used by the `display(self_) -> (...)` form.
+//!
+//! Without the `std` feature, only the global switch is available.
+//!
+//! [`set_enabled`]: fn.set_enabled.html
+//! [`DisplayChain::redacted`]: ../struct.DisplayChain.html#method.redacted
+//! [`DisplayChain::internal`]: ../struct.DisplayChain.html#method.internal
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// Overrides `ENABLED` while a `DisplayChain` is rendered.
+    static OVERRIDE: ::std::cell::Cell<Option<bool>> = const { ::std::cell::Cell::new(None) };
+}
+
+/// Enables or disables redaction for the whole process.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if sensitive fields are currently redacted on this
+/// thread.
+pub fn is_enabled() -> bool {
+    #[cfg(feature = "std")]
+    {
+        if let Some(enabled) = OVERRIDE.with(|o| o.get()) {
+            return enabled;
+        }
+    }
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` with redaction forced on or off on this thread, or unchanged
+/// for `None`.
+#[cfg(feature = "std")]
+pub(crate) fn with_override<R, F: FnOnce() -> R>(enabled: Option<bool>, f: F) -> R {
+    struct Restore(Option<bool>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OVERRIDE.with(|o| o.set(self.0));
+        }
+    }
+
+    if enabled.is_none() {
+        return f();
+    }
+    let _restore = Restore(OVERRIDE.with(|o| o.replace(enabled)));
+    f()
+}
+
+/// A field marked as `sensitive`, which displays as `[REDACTED]` while
+/// redaction is enabled.
+///
+/// It dereferences to the field, so that methods can still be called on it
+/// in `display` clauses.
+#[doc(hidden)]
+pub struct Sensitive<'a, T: 'a + ?Sized>(pub &'a T);
+
+impl<'a, T: ?Sized> Deref for Sensitive<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<'a, T: fmt::Display + ?Sized> fmt::Display for Sensitive<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if is_enabled() {
+            f.write_str("[REDACTED]")
+        } else {
+            fmt::Display::fmt(self.0, f)
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug + ?Sized> fmt::Debug for Sensitive<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if is_enabled() {
+            f.write_str("[REDACTED]")
+        } else {
+            fmt::Debug::fmt(self.0, f)
+        }
+    }
+}
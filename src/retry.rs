@@ -0,0 +1,355 @@
+//! Retrying operations which fail with a retryable error.
+//!
+//! [`retry`] runs an operation until it succeeds, its error is not
+//! retryable according to [`ChainedError::any_retryable`], or the
+//! [`Policy`] gives up. Attempts are separated by exponentially growing
+//! delays. An error which is not retryable on the first attempt is returned
+//! as it is. Otherwise the returned error has a kind built by the caller
+//! and an [`Attempts`] as its cause, recording the number, elapsed time and
+//! error of every attempt.
+//!
+//! [`retry_with`] takes the [`Clock`] and [`Sleeper`] to use, so that
+//! retries can be tested without waiting:
+//!
+//! ```
+//! # #[macro_use] extern crate error_chain;
+//! use std::cell::Cell;
+//! use std::time::{Duration, Instant};
+//!
+//! use error_chain::retry::{self, Clock, Policy, Sleeper};
+//! use error_chain::ChainedError;
+//!
+//! error_chain! {
+//!     errors {
+//!         Unavailable {
+//!             display("service unavailable")
+//!             retryable
+//!         }
+//!     }
+//! }
+//!
+//! struct FakeTime(Instant, Cell<Duration>);
+//!
+//! impl Clock for FakeTime {
+//!     fn now(&self) -> Instant {
+//!         self.0 + self.1.get()
+//!     }
+//! }
+//!
+//! impl Sleeper for FakeTime {
+//!     fn sleep(&self, duration: Duration) {
+//!         self.1.set(self.1.get() + duration);
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let time = FakeTime(Instant::now(), Cell::new(Duration::from_secs(0)));
+//! let policy = Policy::new()
+//!     .max_attempts(3)
+//!     .initial_delay(Duration::from_millis(100));
+//! let res: Result<()> = retry::retry_with(
+//!     &policy,
+//!     &time,
+//!     &time,
+//!     || Err(ErrorKind::Unavailable.into()),
+//!     |attempts| format!("failed after {} attempts", attempts.len()),
+//! );
+//!
+//! let e = res.unwrap_err();
+//! assert_eq!(e.to_string(), "failed after 3 attempts");
+//! assert_eq!(
+//!     e.iter().nth(1).unwrap().to_string(),
+//!     "attempt 1 after 0ns: service unavailable; \
+//!      attempt 2 after 100ms: service unavailable; \
+//!      attempt 3 after 300ms: service unavailable"
+//! );
+//! assert!(!e.any_retryable());
+//! # }
+//! ```
+//!
+//! [`retry`]: fn.retry.html
+//! [`retry_with`]: fn.retry_with.html
+//! [`Policy`]: struct.Policy.html
+//! [`Attempts`]: struct.Attempts.html
+//! [`Clock`]: trait.Clock.html
+//! [`Sleeper`]: trait.Sleeper.html
+//! [`ChainedError::any_retryable`]: ../trait.ChainedError.html#method.any_retryable
+
+use std::cmp;
+use std::error;
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ChainedError;
+
+/// When and how many times [`retry`] runs an operation again.
+///
+/// The first retry waits for the initial delay, and every following one
+/// waits `multiplier` times longer than the previous, up to the maximum
+/// delay.
+///
+/// [`retry`]: fn.retry.html
+#[derive(Debug, Clone)]
+pub struct Policy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    multiplier: u32,
+    max_delay: Duration,
+}
+
+impl Policy {
+    /// Three attempts, starting with a delay of 100ms which doubles up to
+    /// 10s.
+    pub fn new() -> Policy {
+        Policy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            multiplier: 2,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    /// The number of times the operation is run at most, including the
+    /// first one. `0` is treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// The delay before the first retry.
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// The factor applied to the delay after every retry.
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// The longest delay between two attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// The delay before attempt `attempt + 1`, starting from 1.
+    fn delay(&self, attempt: u32) -> Duration {
+        let mut delay = cmp::min(self.initial_delay, self.max_delay);
+        for _ in 1..attempt {
+            if delay == self.max_delay {
+                break;
+            }
+            delay = cmp::min(delay.saturating_mul(self.multiplier), self.max_delay);
+        }
+        delay
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Policy {
+        Policy::new()
+    }
+}
+
+/// The source of the current time, used to measure how long the attempts
+/// took.
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> Instant;
+}
+
+/// Waits between two attempts.
+pub trait Sleeper {
+    /// Blocks for `duration`.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the system, `Instant::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Blocks the current thread with `thread::sleep`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Runs `op` until it succeeds, following `policy`.
+///
+/// The operation is run again only if its error is retryable according to
+/// [`ChainedError::any_retryable`]. If the first attempt fails with an
+/// error which is not retryable, that error is returned unchanged.
+/// Otherwise, once an attempt fails with an error which is not retryable
+/// or `policy` gives up, `summary` builds the kind of the returned error,
+/// whose cause is an [`Attempts`] recording every failure.
+///
+/// [`ChainedError::any_retryable`]: ../trait.ChainedError.html#method.any_retryable
+/// [`Attempts`]: struct.Attempts.html
+#[track_caller]
+pub fn retry<T, E, F, S, K>(policy: &Policy, op: F, summary: S) -> Result<T, E>
+where
+    E: ChainedError,
+    F: FnMut() -> Result<T, E>,
+    S: FnOnce(&Attempts<E>) -> K,
+    K: Into<E::ErrorKind>,
+{
+    retry_with(policy, &SystemClock, &ThreadSleeper, op, summary)
+}
+
+/// Like [`retry`], but measuring time with `clock` and waiting with
+/// `sleeper`.
+///
+/// [`retry`]: fn.retry.html
+#[track_caller]
+#[allow(unknown_lints, bare_trait_objects)]
+pub fn retry_with<T, E, F, S, K>(
+    policy: &Policy,
+    clock: &Clock,
+    sleeper: &Sleeper,
+    mut op: F,
+    summary: S,
+) -> Result<T, E>
+where
+    E: ChainedError,
+    F: FnMut() -> Result<T, E>,
+    S: FnOnce(&Attempts<E>) -> K,
+    K: Into<E::ErrorKind>,
+{
+    let start = clock.now();
+    let mut attempts = Attempts {
+        attempts: Vec::new(),
+    };
+    loop {
+        let number = attempts.len() as u32 + 1;
+        let error = match op() {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        let elapsed = clock.now().duration_since(start);
+        let retryable = error.any_retryable();
+        if !retryable && number == 1 {
+            return Err(error);
+        }
+        attempts.attempts.push(Attempt {
+            number,
+            elapsed,
+            error,
+        });
+        if !retryable || number >= policy.max_attempts {
+            let kind = summary(&attempts);
+            return Err(E::with_chain(attempts, kind));
+        }
+        sleeper.sleep(policy.delay(number));
+    }
+}
+
+/// A failed run of the operation given to [`retry`].
+///
+/// [`retry`]: fn.retry.html
+#[derive(Debug)]
+pub struct Attempt<E> {
+    number: u32,
+    elapsed: Duration,
+    error: E,
+}
+
+impl<E> Attempt<E> {
+    /// The number of the attempt, starting from 1.
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// The time between the start of the first attempt and the failure of
+    /// this one.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The error of the attempt.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Consumes `self`, returning the error of the attempt.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+/// Every failed attempt of [`retry`], used as the cause of the error it
+/// returns.
+///
+/// It has no cause of its own: the errors of the attempts are reached with
+/// [`attempts`], so that the retryable errors they hold don't make the
+/// returned error retryable again for an outer [`retry`].
+///
+/// [`attempts`]: #method.attempts
+///
+/// [`retry`]: fn.retry.html
+#[derive(Debug)]
+pub struct Attempts<E> {
+    attempts: Vec<Attempt<E>>,
+}
+
+impl<E> Attempts<E> {
+    /// The attempts, in order.
+    pub fn attempts(&self) -> &[Attempt<E>] {
+        &self.attempts
+    }
+
+    /// Consumes `self`, returning the attempts in order.
+    pub fn into_attempts(self) -> Vec<Attempt<E>> {
+        self.attempts
+    }
+
+    /// The number of attempts.
+    pub fn len(&self) -> usize {
+        self.attempts.len()
+    }
+
+    /// Returns `true` if there was no attempt.
+    pub fn is_empty(&self) -> bool {
+        self.attempts.is_empty()
+    }
+
+    /// The last attempt.
+    pub fn last(&self) -> Option<&Attempt<E>> {
+        self.attempts.last()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Attempts<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, attempt) in self.attempts.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(
+                f,
+                "attempt {} after {:?}: {}",
+                attempt.number, attempt.elapsed, attempt.error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: error::Error> error::Error for Attempts<E> {
+    #[cfg(not(has_error_description_deprecated))]
+    fn description(&self) -> &str {
+        "failed attempts"
+    }
+}
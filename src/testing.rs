@@ -0,0 +1,192 @@
+//! Helpers for testing code that returns error_chain errors.
+//!
+//! The assertion macros, [`assert_kind!`], [`assert_chain_contains!`] and
+//! [`assert_display_chain_eq!`], accept either an error or a `Result`, and
+//! print the full [`display_chain`] of the error when they fail.
+//!
+//! [`assert_kind!`]: ../macro.assert_kind.html
+//! [`assert_chain_contains!`]: ../macro.assert_chain_contains.html
+//! [`assert_display_chain_eq!`]: ../macro.assert_display_chain_eq.html
+//! [`display_chain`]: ../trait.ChainedError.html#method.display_chain
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use std::error;
+
+use erased;
+use ChainedError;
+
+/// Gives access to the error of an assertion macro's argument, which is
+/// either an error or a `Result`.
+#[doc(hidden)]
+pub trait AsChainedError {
+    /// The error type.
+    type Error: ChainedError;
+
+    /// Returns the error, or `None` for an `Ok` result.
+    fn as_chained_error(&self) -> Option<&Self::Error>;
+}
+
+impl<E: ChainedError> AsChainedError for E {
+    type Error = E;
+
+    fn as_chained_error(&self) -> Option<&E> {
+        Some(self)
+    }
+}
+
+impl<T, E: ChainedError> AsChainedError for Result<T, E> {
+    type Error = E;
+
+    fn as_chained_error(&self) -> Option<&E> {
+        self.as_ref().err()
+    }
+}
+
+/// Returns `true` if an error of type `T` is part of the chain of `e`.
+///
+/// This includes the errors held by `foreign_links` variants, which are not
+/// yielded by `iter` as they share the `Display` of the error wrapping them.
+pub fn chain_contains<T, E>(e: &E) -> bool
+where
+    T: error::Error + 'static,
+    E: ChainedError,
+{
+    e.iter().any(|e| {
+        e.is::<T>()
+            || erased::extract::<E>(e)
+                .and_then(|e| e.foreign_error())
+                .is_some_and(|e| e.is::<T>())
+    })
+}
+
+/// Returns the `Display` of every error of the chain of `e`.
+pub fn display_chain_strings<E: ChainedError>(e: &E) -> Vec<String> {
+    e.iter().map(|e| e.to_string()).collect()
+}
+
+/// Asserts that an error, or the error of a `Result`, is of the given kind.
+///
+/// ```
+/// # #[macro_use] extern crate error_chain;
+/// # fn main() {
+/// error_chain! {
+///     errors {
+///         NotFound(path: String)
+///     }
+/// }
+///
+/// fn open(path: &str) -> Result<()> {
+///     bail!(ErrorKind::NotFound(path.into()))
+/// }
+///
+/// assert_kind!(open("a"), ErrorKind::NotFound(..));
+/// assert_kind!(Error::from("x"), ErrorKind::Msg(..) | ErrorKind::NotFound(..));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_kind {
+    ($e:expr, $($pat:pat)|+) => {{
+        #[allow(unused_imports)]
+        use $crate::testing::AsChainedError;
+        match ($e).as_chained_error() {
+            Some(e) => match *$crate::ChainedError::kind(e) {
+                $($pat)|+ => {}
+                _ => panic!(
+                    "assertion failed: kind of `{}` matches `{}`\n{}",
+                    stringify!($e),
+                    stringify!($($pat)|+),
+                    $crate::ChainedError::display_chain(e)
+                ),
+            },
+            None => panic!(
+                "assertion failed: kind of `{}` matches `{}`\nexpected an error, got `Ok`",
+                stringify!($e),
+                stringify!($($pat)|+)
+            ),
+        }
+    }};
+}
+
+/// Asserts that the chain of an error, or of the error of a `Result`,
+/// contains an error of the given type.
+///
+/// ```
+/// # #[macro_use] extern crate error_chain;
+/// # fn main() {
+/// error_chain! {
+///     foreign_links {
+///         Io(::std::io::Error);
+///     }
+/// }
+///
+/// fn read() -> Result<String> {
+///     Ok(::std::fs::read_to_string("/does/not/exist")?)
+/// }
+///
+/// assert_chain_contains!(read(), ::std::io::Error);
+/// assert_chain_contains!(read().chain_err(|| "outer"), ::std::io::Error);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_chain_contains {
+    ($e:expr, $t:ty) => {{
+        #[allow(unused_imports)]
+        use $crate::testing::AsChainedError;
+        match ($e).as_chained_error() {
+            Some(e) => {
+                if !$crate::testing::chain_contains::<$t, _>(e) {
+                    panic!(
+                        "assertion failed: chain of `{}` contains `{}`\n{}",
+                        stringify!($e),
+                        stringify!($t),
+                        $crate::ChainedError::display_chain(e)
+                    );
+                }
+            }
+            None => panic!(
+                "assertion failed: chain of `{}` contains `{}`\nexpected an error, got `Ok`",
+                stringify!($e),
+                stringify!($t)
+            ),
+        }
+    }};
+}
+
+/// Asserts that the `Display` of every error of the chain of an error, or of
+/// the error of a `Result`, is equal to the given list.
+///
+/// ```
+/// # #[macro_use] extern crate error_chain;
+/// # fn main() {
+/// error_chain! {}
+///
+/// let e = Error::from("inner").chain_err(|| "outer");
+/// assert_display_chain_eq!(e, ["outer", "inner"]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_display_chain_eq {
+    ($e:expr, [$($msg:expr),* $(,)*]) => {{
+        #[allow(unused_imports)]
+        use $crate::testing::AsChainedError;
+        match ($e).as_chained_error() {
+            Some(e) => {
+                let actual = $crate::testing::display_chain_strings(e);
+                let expected: &[&str] = &[$($msg),*];
+                if actual != expected {
+                    panic!(
+                        "assertion failed: display chain of `{}` equals `{:?}`\n{}",
+                        stringify!($e),
+                        expected,
+                        $crate::ChainedError::display_chain(e)
+                    );
+                }
+            }
+            None => panic!(
+                "assertion failed: display chain of `{}` is not empty\nexpected an error, got `Ok`",
+                stringify!($e)
+            ),
+        }
+    }};
+}
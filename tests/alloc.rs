@@ -0,0 +1,92 @@
+//! Checks that errors without a cause or backtrace are built without
+//! allocating. This is a separate test binary as it installs a global
+//! allocator.
+
+#![cfg(feature = "std")]
+
+#[macro_use]
+extern crate error_chain;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+error_chain! {
+    foreign_links {
+        ParseChar(::std::char::ParseCharError);
+    }
+
+    errors {
+        Unexpected(c: char, at: usize)
+    }
+}
+
+fn parse(c: char, at: usize) -> Result<()> {
+    Err(ErrorKind::Unexpected(c, at))?
+}
+
+fn parse_all() -> Result<()> {
+    parse('x', 3)?;
+    Ok(())
+}
+
+#[test]
+fn no_allocation_without_backtrace() {
+    let _guard = error_chain::suppress_backtraces();
+    // The first error reads `RUST_BACKTRACE`.
+    let _ = Error::from_kind(ErrorKind::Unexpected('x', 0));
+
+    assert_eq!(
+        allocations(|| {
+            let e = Error::from_kind(ErrorKind::Unexpected('x', 0));
+            assert!(e.backtrace().is_none());
+        }),
+        0
+    );
+    assert_eq!(
+        allocations(|| {
+            assert!(parse_all().is_err());
+        }),
+        0
+    );
+    // Foreign errors are stored in the kind.
+    let cause = "ab".parse::<char>().unwrap_err();
+    assert_eq!(
+        allocations(|| {
+            let _ = Error::from(cause.clone());
+        }),
+        0
+    );
+    // Chaining boxes the cause.
+    assert_eq!(
+        allocations(|| {
+            let _ = Error::with_chain(cause, ErrorKind::Unexpected('x', 0));
+        }),
+        1
+    );
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "derive")]
+
+#[macro_use]
+extern crate error_chain;
+extern crate error_chain as renamed;
+
+use error_chain::ChainedError;
+
+mod inner {
+    error_chain! {
+        errors {
+            Timeout
+        }
+    }
+}
+
+#[derive(Debug, ErrorChain)]
+pub enum ErrorKind {
+    #[error_chain(msg)]
+    Msg(String),
+
+    #[error_chain(link = inner::Error)]
+    Inner(inner::ErrorKind),
+
+    #[error_chain(foreign)]
+    Fmt(::std::fmt::Error),
+
+    #[error_chain(foreign)]
+    #[cfg(unix)]
+    Io(::std::io::Error),
+
+    /// Nothing to display.
+    Unit,
+
+    #[error_chain(description = "bad input")]
+    #[error_chain(display = "bad {1} at {0}, {{escaped}} {1:?}")]
+    Tuple(u32, String),
+
+    #[error_chain(display = "{} then {}")]
+    InOrder(u8, u8),
+
+    #[error_chain(display = "{name} is {value:>4}")]
+    Struct { name: &'static str, value: u32 },
+
+    #[error_chain(display = "{value:>width$.precision$} {0:1$}")]
+    Padded {
+        value: f64,
+        width: usize,
+        precision: usize,
+    },
+}
+
+mod renamed_crate {
+    #[derive(Debug, ErrorChain)]
+    #[error_chain(crate = ::renamed)]
+    pub enum ErrorKind {
+        Unit,
+    }
+}
+
+#[test]
+fn display_and_description() {
+    assert_eq!(ErrorKind::Unit.to_string(), "Unit");
+    assert_eq!(ErrorKind::Unit.description(), "Unit");
+    let kind = ErrorKind::Tuple(3, "x".into());
+    assert_eq!(kind.to_string(), "bad x at 3, {escaped} \"x\"");
+    assert_eq!(kind.description(), "bad input");
+    assert_eq!(ErrorKind::InOrder(1, 2).to_string(), "1 then 2");
+    let kind = ErrorKind::Struct {
+        name: "answer",
+        value: 42,
+    };
+    assert_eq!(kind.to_string(), "answer is   42");
+    assert_eq!(kind.variant_name(), "Struct");
+    let kind = ErrorKind::Padded {
+        value: 1.5,
+        width: 6,
+        precision: 2,
+    };
+    assert_eq!(kind.to_string(), "  1.50    1.5");
+}
+
+#[test]
+fn crate_path() {
+    let e: renamed_crate::Error = renamed_crate::ErrorKind::Unit.into();
+    assert_eq!(e.to_string(), "Unit");
+}
+
+#[test]
+fn conversions() {
+    let e: Error = "message".into();
+    assert_eq!(e.to_string(), "message");
+    match *e.kind() {
+        ErrorKind::Msg(ref s) => assert_eq!(s, "message"),
+        ref kind => panic!("{:?}", kind),
+    }
+
+    let e: Error = inner::Error::from_kind(inner::ErrorKind::Timeout).into();
+    assert_eq!(e.kind().variant_path(), "Inner::Timeout");
+
+    let e: Error = ::std::fmt::Error.into();
+    assert_eq!(
+        e.to_string(),
+        "an error occurred when formatting an argument"
+    );
+    assert!(e.iter().nth(1).is_none());
+
+    fn fails() -> Result<()> {
+        Err(::std::fmt::Error)?;
+        Ok(())
+    }
+    let e = fails().chain_err(|| ErrorKind::Unit).unwrap_err();
+    let messages: Vec<_> = e.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["Unit", "an error occurred when formatting an argument"]
+    );
+    assert!(e.display_chain().to_string().starts_with("Error: Unit\n"));
+}
+
+#[test]
+fn catalog() {
+    let entry = ErrorKind::catalog()
+        .iter()
+        .find(|e| e.name() == "Unit")
+        .unwrap();
+    assert_eq!(entry.doc(), "Nothing to display.");
+}
+
+mod custom {
+    mod inner {
+        error_chain! {}
+    }
+
+    #[derive(Debug, ErrorChain)]
+    #[error_chain(error = MyError, result_ext = MyResultExt, result = MyResult)]
+    #[error_chain(thin)]
+    pub enum MyErrorKind {
+        #[error_chain(link = inner::Error)]
+        Inner(inner::ErrorKind),
+        Other,
+    }
+
+    #[test]
+    fn custom() {
+        use self::MyResultExt;
+
+        let r: MyResult<()> = Err(::std::fmt::Error).chain_err(|| MyErrorKind::Other);
+        let e: MyError = r.unwrap_err();
+        assert_eq!(e.to_string(), "Other");
+        assert_eq!(
+            ::std::mem::size_of::<MyError>(),
+            ::std::mem::size_of::<usize>()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// Installs a global observer, so kept out of `tests.rs` where it would see
+// the errors created by concurrently running tests.
+
+#![cfg(feature = "std")]
+
+#[macro_use]
+extern crate error_chain;
+
+use error_chain::metrics::{self, AlreadyInstalled, Counter, ErrorEvent, Observer};
+use std::any::type_name;
+use std::sync::Mutex;
+
+mod inner {
+    error_chain! {}
+}
+
+error_chain! {
+    links {
+        Inner(inner::Error, inner::ErrorKind);
+    }
+    foreign_links {
+        Fmt(::std::fmt::Error);
+    }
+    errors {
+        Timeout
+    }
+}
+
+// The observer can only be installed once, so it both counts the errors and
+// records where the last one was created.
+struct Recorder {
+    counter: Counter,
+    last_line: Mutex<Option<u32>>,
+}
+
+impl Observer for Recorder {
+    fn on_error(&self, event: &ErrorEvent) {
+        self.counter.on_error(event);
+        *self.last_line.lock().unwrap() = Some(event.location().line());
+    }
+}
+
+static RECORDER: Recorder = Recorder {
+    counter: Counter::new(),
+    last_line: Mutex::new(None),
+};
+static COUNTER: &Counter = &RECORDER.counter;
+static OTHER: Counter = Counter::new();
+
+fn fails() -> Result<()> {
+    Err(::std::fmt::Error)?;
+    Ok(())
+}
+
+#[test]
+fn observer() {
+    let name = type_name::<Error>();
+
+    // Nothing is recorded without an observer.
+    let _ = Error::from_kind(ErrorKind::Timeout);
+
+    metrics::set_observer(&RECORDER).unwrap();
+    assert_eq!(metrics::set_observer(&OTHER), Err(AlreadyInstalled));
+    let _ = Error::from_kind(ErrorKind::Timeout);
+    let _ = Error::from("msg");
+    let _ = fails();
+    let _: Result<()> = Err(::std::fmt::Error).chain_err(|| ErrorKind::Timeout);
+    let _: Result<()> = None.chain_err(|| "none");
+    let _ = Error::from_kind(ErrorKind::Timeout).chain_err(|| "outer");
+    // Links reuse the state of the linked error, only that one is counted.
+    let _ = Error::from(inner::Error::from("inner"));
+
+    assert_eq!(COUNTER.count(name, "Timeout"), 3);
+    assert_eq!(COUNTER.count(name, "Msg"), 3);
+    assert_eq!(COUNTER.count(name, "Fmt"), 1);
+    assert_eq!(COUNTER.count(type_name::<inner::Error>(), "Msg"), 1);
+    assert_eq!(COUNTER.total(), 8);
+
+    COUNTER.reset();
+    assert_eq!(COUNTER.total(), 0);
+
+    let line = line!() + 1;
+    let _ = Error::from_kind(ErrorKind::Timeout);
+    assert_eq!(*RECORDER.last_line.lock().unwrap(), Some(line));
+
+    let line = line!() + 1;
+    let _: Result<()> = Err(::std::fmt::Error).chain_err(|| "context");
+    assert_eq!(*RECORDER.last_line.lock().unwrap(), Some(line));
+    assert_eq!(COUNTER.total(), 2);
+    assert_eq!(OTHER.total(), 0);
+}
@@ -0,0 +1,53 @@
+#![no_std]
+
+#[macro_use]
+extern crate error_chain;
+
+pub mod inner {
+    error_chain! {
+        errors {
+            Overflow(at: usize) {
+                description("overflow")
+                display("overflow at {}", at)
+            }
+        }
+    }
+}
+
+error_chain! {
+    links {
+        Inner(inner::Error, inner::ErrorKind);
+    }
+
+    foreign_links {
+        Fmt(::core::fmt::Error);
+    }
+
+    errors {
+        Checksum(expected: u8, actual: u8) {
+            description("checksum mismatch")
+            display("checksum mismatch: expected {}, got {}", expected, actual)
+        }
+    }
+}
+
+fn push(len: usize) -> inner::Result<usize> {
+    ensure!(len < 64, inner::ErrorKind::Overflow(len));
+    Ok(len + 1)
+}
+
+pub fn check(frame: &[u8]) -> Result<()> {
+    let sum = frame.iter().fold(0u8, |a, b| a.wrapping_add(*b));
+    if sum != 0 {
+        bail!(ErrorKind::Checksum(0, sum));
+    }
+    push(frame.len()).chain_err(|| "frame too long")?;
+    if frame.is_empty() {
+        bail!("empty frame of {} bytes", frame.len());
+    }
+    Ok(())
+}
+
+pub fn depth(e: &Error) -> usize {
+    e.iter().count()
+}
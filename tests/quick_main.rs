@@ -1,3 +1,4 @@
+#![cfg(feature = "std")]
 #![allow(dead_code)]
 #[macro_use]
 extern crate error_chain;
@@ -0,0 +1,118 @@
+// Flips the global redaction switch, so kept out of `tests.rs` where it
+// would change the output of concurrently running tests.
+
+#![cfg(feature = "std")]
+
+#[macro_use]
+extern crate error_chain;
+
+use error_chain::{redaction, ChainedError};
+
+mod inner {
+    error_chain! {
+        errors {
+            Key(id: u32, secret: String) {
+                display("key {} is {}", id, secret)
+                sensitive(secret)
+            }
+        }
+    }
+}
+
+error_chain! {
+    links {
+        Inner(inner::Error, inner::ErrorKind);
+    }
+    errors {
+        Auth(user: String, token: String) {
+            description("authentication failed")
+            display("failed to auth {} with token {}", user, token)
+            sensitive(token)
+        }
+        Login { user: String, password: String, pin: u16 } {
+            display("cannot log {} in with {}/{}", user, password, pin)
+            sensitive(password, pin)
+        }
+        Plain(s: String) {
+            display("plain {}", s)
+        }
+    }
+}
+
+fn auth() -> Error {
+    ErrorKind::Auth("alice".into(), "hunter2".into()).into()
+}
+
+#[test]
+fn redaction() {
+    let _guard = error_chain::suppress_backtraces();
+
+    // Disabled by default.
+    assert_eq!(
+        auth().to_string(),
+        "failed to auth alice with token hunter2"
+    );
+    assert_eq!(
+        format!("{:?}", auth().kind()),
+        r#"Auth("alice", "hunter2")"#
+    );
+
+    // Enabled for one rendering.
+    assert_eq!(
+        auth().display_chain().redacted().to_string(),
+        "Error: failed to auth alice with token [REDACTED]\n"
+    );
+    assert_eq!(
+        auth().to_string(),
+        "failed to auth alice with token hunter2"
+    );
+
+    // Links render their own sensitive fields.
+    let e = Error::with_chain(
+        inner::Error::from(inner::ErrorKind::Key(7, "s3cr3t".into())),
+        ErrorKind::Plain("visible".into()),
+    );
+    assert_eq!(
+        e.display_chain().redacted().to_string(),
+        "Error: plain visible\nCaused by: key 7 is [REDACTED]\n"
+    );
+
+    redaction::set_enabled(true);
+    assert!(redaction::is_enabled());
+    assert_eq!(
+        auth().to_string(),
+        "failed to auth alice with token [REDACTED]"
+    );
+    assert_eq!(
+        format!("{:?}", auth().kind()),
+        r#"Auth("alice", [REDACTED])"#
+    );
+    let login = ErrorKind::Login {
+        user: "bob".into(),
+        password: "pa55".into(),
+        pin: 1234,
+    };
+    assert_eq!(
+        login.to_string(),
+        "cannot log bob in with [REDACTED]/[REDACTED]"
+    );
+    assert_eq!(
+        format!("{:?}", login),
+        r#"Login { user: "bob", password: [REDACTED], pin: [REDACTED] }"#
+    );
+    assert_eq!(auth().description(), "authentication failed");
+
+    // The internal rendering shows everything.
+    assert_eq!(
+        auth().display_chain().internal().to_string(),
+        "Error: failed to auth alice with token hunter2\n"
+    );
+    assert!(redaction::is_enabled());
+
+    redaction::set_enabled(false);
+    assert!(!redaction::is_enabled());
+    assert_eq!(
+        auth().to_string(),
+        "failed to auth alice with token hunter2"
+    );
+}
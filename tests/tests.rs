@@ -233,6 +233,10 @@ fn has_backtrace_depending_on_env() {
 
     cmd.env("RUST_BACKTRACE", "1");
     assert_eq!(cmd.status().unwrap().code().unwrap(), 1);
+
+    // Unless suppressed on the current thread
+    cmd.arg("suppressed");
+    assert_eq!(cmd.status().unwrap().code().unwrap(), 0);
 }
 
 #[test]
@@ -455,6 +459,20 @@ mod attributes_test {
     }
 }
 
+#[test]
+fn unit_variant_before_attribute() {
+    error_chain! {
+        errors {
+            First
+            /// The second error.
+            Second
+        }
+    }
+
+    assert_eq!(ErrorKind::First.to_string(), "First");
+    assert_eq!(ErrorKind::Second.to_string(), "Second");
+}
+
 #[test]
 fn with_result() {
     error_chain! {
@@ -505,7 +523,7 @@ mod multiple_error_same_mod {
     error_chain! {}
 }
 
-#[doc(test)]
+#[cfg(test)]
 #[deny(dead_code)]
 mod allow_dead_code {
     error_chain! {}
@@ -530,6 +548,7 @@ fn rustup_regression() {
 }
 
 #[test]
+#[allow(clippy::single_match)]
 fn error_patterns() {
     error_chain! {
         links { }
@@ -633,6 +652,7 @@ fn types_declarations() {
 #[test]
 /// Calling chain_err over a `Result` containing an error to get a chained error
 /// and constructing a MyError directly, passing it an error should be equivalent.
+#[allow(clippy::invisible_characters)]
 fn rewrapping() {
     use std::env::VarError::{self, NotPresent, NotUnicode};
 
@@ -717,3 +737,1751 @@ fn skipping_msg_variant() {
         ErrorKind::__Nonexhaustive {} => {}
     }
 }
+
+#[test]
+fn collect_errors() {
+    use error_chain::{CollectErrors, Failures};
+    use std::num::ParseIntError;
+
+    error_chain! {
+        errors {
+            Batch(failed: usize, total: usize) {
+                description("batch failed")
+                display("{} of {} items failed", failed, total)
+            }
+        }
+    }
+
+    fn summary(f: &Failures<ParseIntError>) -> ErrorKind {
+        ErrorKind::Batch(f.len(), f.total())
+    }
+
+    let ok: Result<Vec<i32>> = vec!["1", "2"]
+        .into_iter()
+        .map(|s| s.parse())
+        .collect_errors(summary);
+    assert_eq!(ok.unwrap(), vec![1, 2]);
+
+    let err = vec!["1", "a", "3", "b", "c"]
+        .into_iter()
+        .map(|s| s.parse::<i32>())
+        .collect_errors::<Vec<_>, Error, _, _>(summary)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "3 of 5 items failed");
+    let failures = std::error::Error::source(&err)
+        .unwrap()
+        .downcast_ref::<Failures<ParseIntError>>()
+        .unwrap();
+    let indices: Vec<usize> = failures.errors().iter().map(|&(i, _)| i).collect();
+    assert_eq!(indices, vec![1, 3, 4]);
+
+    let limited = vec!["1", "a", "3", "b", "c"]
+        .into_iter()
+        .map(|s| s.parse::<i32>())
+        .collect_errors_max::<Vec<_>, Error, _, _>(2, summary)
+        .unwrap_err();
+    assert_eq!(limited.to_string(), "2 of 4 items failed");
+
+    let first = vec!["1", "a", "3", "b"]
+        .into_iter()
+        .map(|s| s.parse::<i32>())
+        .collect_errors_max::<Vec<_>, Error, _, _>(0, summary)
+        .unwrap_err();
+    assert_eq!(first.to_string(), "1 of 2 items failed");
+}
+
+#[test]
+fn variant_names() {
+    mod inner {
+        error_chain! {
+            errors { Timeout }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+        foreign_links {
+            Io(::std::io::Error);
+        }
+        errors {
+            Custom(s: String) {}
+            #[cfg(not(test))]
+            Disabled
+        }
+    }
+
+    assert_eq!(ErrorKind::VARIANTS, &["Inner", "Io", "Msg", "Custom"]);
+    assert_eq!(inner::ErrorKind::VARIANTS, &["Msg", "Timeout"]);
+
+    assert_eq!(ErrorKind::Msg("x".into()).variant_name(), "Msg");
+    assert_eq!(ErrorKind::Custom("x".into()).variant_name(), "Custom");
+
+    let linked = ErrorKind::from(inner::ErrorKind::Timeout);
+    assert_eq!(linked.variant_name(), "Inner");
+    assert_eq!(linked.variant_path(), "Inner::Timeout");
+    assert_eq!(ErrorKind::Custom("x".into()).variant_path(), "Custom");
+}
+
+#[cfg(test)]
+mod testing_macros {
+    use std::io;
+
+    mod inner {
+        error_chain! {
+            foreign_links {
+                Io(::std::io::Error);
+            }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+        errors {
+            Timeout
+            NotFound(path: String) {
+                display("not found: {}", path)
+            }
+        }
+    }
+
+    fn io_error() -> inner::Result<()> {
+        Err(io::Error::other("disk on fire"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn assert_kind() {
+        assert_kind!(Error::from_kind(ErrorKind::Timeout), ErrorKind::Timeout);
+        let res: Result<()> = Err(ErrorKind::NotFound("a".into()).into());
+        assert_kind!(res, ErrorKind::NotFound(..));
+        assert_kind!(&res, ErrorKind::Timeout | ErrorKind::NotFound(_));
+        let linked: Error = io_error().unwrap_err().into();
+        assert_kind!(linked, ErrorKind::Inner(inner::ErrorKind::Io(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: not found: a")]
+    fn assert_kind_prints_chain() {
+        assert_kind!(
+            Error::from(ErrorKind::NotFound("a".into())),
+            ErrorKind::Timeout
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "got `Ok`")]
+    fn assert_kind_ok() {
+        let res: Result<()> = Ok(());
+        assert_kind!(res, ErrorKind::Timeout);
+    }
+
+    #[test]
+    fn assert_chain_contains() {
+        assert_chain_contains!(io_error(), io::Error);
+        assert_chain_contains!(io_error().chain_err(|| "outer"), io::Error);
+        assert_chain_contains!(io_error().chain_err(|| "outer"), inner::Error);
+        let boxed: Result<()> = Err(io::Error::other("x")).chain_err(|| "y");
+        assert_chain_contains!(boxed, io::Error);
+    }
+
+    #[test]
+    #[should_panic(expected = "Caused by: disk on fire")]
+    fn assert_chain_contains_prints_chain() {
+        assert_chain_contains!(io_error().chain_err(|| "outer"), ::std::fmt::Error);
+    }
+
+    #[test]
+    fn assert_display_chain_eq() {
+        let e = io_error().chain_err(|| ErrorKind::NotFound("a".into()));
+        assert_display_chain_eq!(e, ["not found: a", "disk on fire"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: not found: a")]
+    fn assert_display_chain_eq_prints_chain() {
+        let e = io_error().chain_err(|| ErrorKind::NotFound("a".into()));
+        assert_display_chain_eq!(e, ["not found: a"]);
+    }
+}
+
+#[test]
+fn normalized_display_chain() {
+    use error_chain::ChainedError;
+
+    error_chain! {
+        foreign_links {
+            Io(::std::io::Error);
+        }
+    }
+
+    let e: Error =
+        ::std::io::Error::other("bad pointer 0x7ffd5a1c at /usr/src/app/main.rs:12:5").into();
+    let e = e.chain_err(|| "reading \"C:\\Users\\bob\\data.bin\" failed");
+    assert_eq!(
+        e.display_chain().normalized().to_string(),
+        "Error: reading \"[..]/data.bin\" failed\n\
+         Caused by: bad pointer [ADDR] at [..]/main.rs:12:5\n"
+    );
+
+    // Relative paths, ratios and URLs are left alone.
+    let e = Error::from("1/2 of src/lib.rs from http://example.com/a (0x)");
+    assert_eq!(
+        e.display_chain().normalized().to_string(),
+        "Error: 1/2 of src/lib.rs from http://example.com/a (0x)\n"
+    );
+}
+
+mod thin {
+    mod inner {
+        error_chain! {
+            errors {
+                Inner
+            }
+        }
+    }
+
+    mod outer {
+        error_chain! {
+            links {
+                Inner(super::inner::Error, super::inner::ErrorKind);
+            }
+
+            foreign_links {
+                Fmt(::std::fmt::Error);
+            }
+
+            errors {
+                Outer
+            }
+
+            thin
+        }
+    }
+
+    mod regular {
+        error_chain! {
+            links {
+                Outer(super::outer::Error, super::outer::ErrorKind);
+            }
+        }
+    }
+
+    #[test]
+    fn thin() {
+        use self::outer::ResultExt;
+
+        assert_eq!(
+            ::std::mem::size_of::<outer::Error>(),
+            ::std::mem::size_of::<usize>()
+        );
+
+        let e: outer::Error = inner::Error::from_kind(inner::ErrorKind::Inner).into();
+        match *e.kind() {
+            outer::ErrorKind::Inner(inner::ErrorKind::Inner) => {}
+            _ => panic!("{:?}", e),
+        }
+
+        let e = Err::<(), _>(e)
+            .chain_err(|| outer::ErrorKind::Outer)
+            .unwrap_err();
+        assert!(matches!(*e.kind(), outer::ErrorKind::Outer));
+        assert_eq!(e.iter().count(), 2);
+
+        let e: outer::Error = ::std::fmt::Error.into();
+        assert!(matches!(*e.kind(), outer::ErrorKind::Fmt(_)));
+
+        // Thin errors can be linked into regular ones, keeping their state.
+        let e: regular::Error = outer::Error::from("msg").chain_err(|| "chained").into();
+        assert!(matches!(
+            *e.kind(),
+            regular::ErrorKind::Outer(outer::ErrorKind::Msg(_))
+        ));
+        assert_eq!(e.iter().count(), 2);
+        let kind: regular::ErrorKind = e.into();
+        assert!(matches!(kind, regular::ErrorKind::Outer(_)));
+    }
+}
+
+mod opaque_links {
+    mod upstream {
+        error_chain! {
+            errors {
+                Timeout(secs: u64) {
+                    description("timeout")
+                    display("timed out after {}s", secs)
+                }
+            }
+        }
+    }
+
+    error_chain! {
+        opaque_links {
+            Upstream #[doc = "An error of another crate."];
+        }
+    }
+
+    #[test]
+    fn from_chained() {
+        let upstream =
+            upstream::Error::with_chain(::std::fmt::Error, upstream::ErrorKind::Timeout(3));
+        let backtrace = upstream.backtrace().map(|b| b as *const _);
+
+        let e = Error::from_chained(upstream);
+        assert_eq!(e.to_string(), "timed out after 3s");
+        assert_eq!(e.iter().count(), 2);
+        assert_eq!(e.backtrace().map(|b| b as *const _), backtrace);
+        assert_eq!(e.kind().variant_name(), "Upstream");
+        assert_eq!(e.kind().variant_path(), "Upstream::Timeout");
+
+        match e.into() {
+            ErrorKind::Upstream(kind) => match kind.downcast::<upstream::ErrorKind>() {
+                Ok(upstream::ErrorKind::Timeout(3)) => {}
+                other => panic!("{:?}", other),
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn manual_kind() {
+        use error_chain::classify::Severity;
+        use error_chain::{ChainedErrorKind, HttpStatus, OpaqueKind};
+        use std::fmt;
+
+        #[derive(Debug)]
+        struct Manual;
+
+        impl fmt::Display for Manual {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("manual")
+            }
+        }
+
+        impl HttpStatus for Manual {
+            fn http_status(&self) -> u16 {
+                503
+            }
+        }
+
+        impl ChainedErrorKind for Manual {
+            fn description(&self) -> &str {
+                "manual"
+            }
+
+            fn variant_name(&self) -> &'static str {
+                "Manual"
+            }
+        }
+
+        let kind = OpaqueKind::new(Manual);
+        assert_eq!(kind.variant_path(), "Manual");
+        assert_eq!(kind.code(), None);
+        assert_eq!(kind.severity(), Severity::Error);
+        assert!(!kind.is_retryable());
+        assert_eq!(kind.help(), None);
+    }
+
+    #[test]
+    fn from_chained_self() {
+        let e = Error::from_chained(Error::from("inner"));
+        assert_eq!(e.to_string(), "inner");
+        assert_eq!(e.kind().variant_path(), "Upstream::Msg");
+    }
+}
+
+#[cfg(feature = "anyhow")]
+mod anyhow_interop {
+    extern crate anyhow;
+
+    use error_chain::anyhow::{Anyhow, Chained, IntoAnyhow};
+
+    error_chain! {
+        foreign_links {
+            Dynamic(Anyhow);
+        }
+    }
+
+    fn parse() -> Result<()> {
+        Err(Error::from("bad digit").chain_err(|| "invalid header"))
+    }
+
+    fn run() -> anyhow::Result<()> {
+        parse().into_anyhow()?;
+        Ok(())
+    }
+
+    #[test]
+    fn question_mark() {
+        let e = run().unwrap_err();
+        let messages: Vec<_> = e.chain().map(|e| e.to_string()).collect();
+        assert_eq!(messages, ["invalid header", "bad digit"]);
+        assert!(e.downcast_ref::<Chained>().is_some());
+    }
+
+    #[test]
+    fn into_anyhow() {
+        let e = Error::with_chain(::std::fmt::Error, "outer").chain_err(|| "outermost");
+        let backtrace = e.backtrace().map(|b| b as *const _);
+
+        let e = Err::<(), _>(e).into_anyhow().unwrap_err();
+        let messages: Vec<_> = e.chain().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "outermost",
+                "outer",
+                "an error occurred when formatting an argument"
+            ]
+        );
+
+        let chained = e.downcast::<Chained>().unwrap();
+        assert_eq!(chained.backtrace().map(|b| b as *const _), backtrace);
+        let e = chained.into_inner().downcast::<Error>().unwrap();
+        assert_eq!(e.to_string(), "outermost");
+    }
+
+    #[test]
+    fn anyhow_cause() {
+        let e = anyhow::anyhow!("disk full").context("cannot save");
+
+        let e: Error = Anyhow::from(e).into();
+        assert!(matches!(*e.kind(), ErrorKind::Dynamic(_)));
+        assert_eq!(e.iter().count(), 2);
+
+        let e = Err::<(), _>(Anyhow::from(
+            anyhow::anyhow!("disk full").context("cannot save"),
+        ))
+        .chain_err(|| "autosave failed")
+        .unwrap_err();
+        let messages: Vec<_> = e.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages, ["autosave failed", "cannot save", "disk full"]);
+    }
+}
+
+#[cfg(feature = "std")]
+mod io_conversion {
+    use std::io;
+
+    mod inner {
+        error_chain! {
+            into_io
+
+            errors {
+                Locked {
+                    io_kind(::std::io::ErrorKind::WouldBlock)
+                }
+            }
+        }
+    }
+
+    mod manual {
+        use std::io;
+
+        error_chain! {}
+
+        impl From<Error> for io::Error {
+            fn from(e: Error) -> io::Error {
+                io::Error::new(io::ErrorKind::Other, e.to_string())
+            }
+        }
+    }
+
+    error_chain! {
+        into_io
+
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        foreign_links {
+            Io(::std::io::Error);
+            Fmt(::std::fmt::Error);
+        }
+
+        errors {
+            Denied(user: String) {
+                description("denied")
+                display("denied: {}", user)
+                io_kind(io::ErrorKind::PermissionDenied),
+            }
+            Plain
+        }
+    }
+
+    #[test]
+    fn io_kind() {
+        assert_eq!(
+            ErrorKind::Denied("a".into()).io_kind(),
+            io::ErrorKind::PermissionDenied
+        );
+        assert_eq!(ErrorKind::Plain.io_kind(), io::ErrorKind::Other);
+        assert_eq!(ErrorKind::Msg("a".into()).io_kind(), io::ErrorKind::Other);
+        assert_eq!(
+            ErrorKind::Inner(inner::ErrorKind::Locked).io_kind(),
+            io::ErrorKind::WouldBlock
+        );
+        let e: Error = io::Error::from(io::ErrorKind::NotFound).into();
+        assert_eq!(e.kind().io_kind(), io::ErrorKind::NotFound);
+        let e: Error = ::std::fmt::Error.into();
+        assert_eq!(e.kind().io_kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn round_trip() {
+        let e = Error::with_chain(::std::fmt::Error, ErrorKind::Denied("a".into()));
+        let e = io::Error::from(e);
+        assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(e.to_string(), "denied: a");
+        assert!(e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<error_chain::Chained>())
+            .is_some_and(|e| e.is::<Error>()));
+
+        let e = Error::from_io(e).unwrap();
+        assert!(matches!(*e.kind(), ErrorKind::Denied(_)));
+        assert_eq!(e.iter().count(), 2);
+    }
+
+    #[test]
+    fn from_io_unrelated() {
+        let e = io::Error::new(io::ErrorKind::Other, "plain");
+        let e = Error::from_io(e).unwrap_err();
+        assert_eq!(e.to_string(), "plain");
+
+        let e = io::Error::from(inner::Error::from(inner::ErrorKind::Locked));
+        let e = Error::from_io(e).unwrap_err();
+        assert!(inner::Error::from_io(e).is_ok());
+    }
+
+    #[test]
+    fn without_into_io() {
+        let e = io::Error::from(manual::Error::from("by hand"));
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+        assert_eq!(e.to_string(), "by hand");
+    }
+}
+
+mod http_status {
+    use error_chain::http::Problem;
+    use error_chain::HttpStatus;
+
+    mod inner {
+        error_chain! {
+            errors {
+                Gone {
+                    http_status(410)
+                }
+            }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        opaque_links {
+            Upstream;
+        }
+
+        foreign_links {
+            Fmt(::std::fmt::Error);
+        }
+
+        errors {
+            BadInput(field: String) {
+                description("bad input")
+                display("bad \"{}\"", field)
+                http_status(400),
+            }
+            Teapot {
+                http_status(418)
+            }
+        }
+    }
+
+    #[test]
+    fn http_status() {
+        assert_eq!(ErrorKind::BadInput("a".into()).http_status(), 400);
+        assert_eq!(ErrorKind::Msg("a".into()).http_status(), 500);
+        assert_eq!(ErrorKind::Inner(inner::ErrorKind::Gone).http_status(), 410);
+        let e: Error = ::std::fmt::Error.into();
+        assert_eq!(e.http_status(), 500);
+        let e = Error::from_chained(inner::Error::from_kind(inner::ErrorKind::Gone));
+        assert_eq!(e.http_status(), 410);
+    }
+
+    #[test]
+    fn problem() {
+        let e = Error::from("db:\tdown").chain_err(|| ErrorKind::BadInput("na\"me".into()));
+        let problem = Problem::new(&e)
+            .type_uri("https://example.com/probs/bad-input")
+            .instance("/users/1");
+        assert_eq!(problem.status(), 400);
+        assert_eq!(
+            problem.to_json(),
+            r#"{"type":"https://example.com/probs/bad-input","title":"Bad Request","status":400,"detail":"bad \"na\"me\"","instance":"/users/1","causes":["db:\tdown"]}"#
+        );
+
+        let e = Error::from("password is hunter2").chain_err(|| "lookup failed");
+        assert_eq!(
+            Problem::new(&e).hide_internal(true).to_json(),
+            r#"{"type":"about:blank","title":"Internal Server Error","status":500}"#
+        );
+
+        let e = Error::from(ErrorKind::Teapot);
+        assert_eq!(
+            Problem::new(&e).title("Teapot").to_json(),
+            r#"{"type":"about:blank","title":"Teapot","status":418,"detail":"Teapot"}"#
+        );
+    }
+}
+
+mod localize {
+    #[cfg(feature = "std")]
+    use error_chain::ChainedError;
+    use std::fmt;
+
+    mod inner {
+        error_chain! {
+            errors {
+                Gone(id: u32) {
+                    display("{} is gone", id)
+                    message_id("gone")
+                }
+            }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        opaque_links {
+            Upstream;
+        }
+
+        errors {
+            NotFound { path: String, line: u32 } {
+                display("{} not found at line {}", path, line)
+                message_id("not-found")
+            }
+            Auth(user: String, token: String) {
+                display("bad token {} for {}", token, user)
+                message_id("auth")
+                sensitive(token)
+            }
+            Untranslated {
+                message_id("untranslated")
+            }
+        }
+    }
+
+    #[allow(unknown_lints, bare_trait_objects)]
+    fn german(id: &str, args: &[(&str, &fmt::Display)]) -> Option<String> {
+        let args: Vec<_> = args
+            .iter()
+            .map(|&(name, value)| format!("{}={}", name, value))
+            .collect();
+        match id {
+            "gone" => Some(format!("weg ({})", args.join(", "))),
+            "not-found" => Some(format!("nicht gefunden ({})", args.join(", "))),
+            "auth" => Some(format!("Anmeldung fehlgeschlagen ({})", args.join(", "))),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn message_id() {
+        assert_eq!(ErrorKind::Untranslated.message_id(), Some("untranslated"));
+        assert_eq!(ErrorKind::Msg("a".into()).message_id(), None);
+        assert_eq!(
+            ErrorKind::Inner(inner::ErrorKind::Gone(1)).message_id(),
+            Some("gone")
+        );
+        let e = Error::from_chained(inner::Error::from_kind(inner::ErrorKind::Gone(1)));
+        assert_eq!(e.kind().message_id(), Some("gone"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn localized() {
+        let _guard = ::error_chain::suppress_backtraces();
+        let localizer = &german;
+
+        let e = Error::with_chain(
+            inner::Error::from_kind(inner::ErrorKind::Gone(3)),
+            ErrorKind::NotFound {
+                path: "/home/a/x.rs".into(),
+                line: 7,
+            },
+        )
+        .chain_err(|| ErrorKind::Untranslated)
+        .chain_err(|| "plain message");
+        assert_eq!(
+            e.display_chain().localized(localizer).to_string(),
+            "Error: plain message\n\
+             Caused by: Untranslated\n\
+             Caused by: nicht gefunden (path=/home/a/x.rs, line=7)\n\
+             Caused by: weg (id=3)\n"
+        );
+        assert_eq!(
+            e.display_chain()
+                .localized(localizer)
+                .normalized()
+                .to_string(),
+            "Error: plain message\n\
+             Caused by: Untranslated\n\
+             Caused by: nicht gefunden (path=[..]/x.rs, line=7)\n\
+             Caused by: weg (id=3)\n"
+        );
+
+        let e = Error::from(ErrorKind::Inner(inner::ErrorKind::Gone(4)));
+        assert_eq!(
+            e.display_chain().localized(localizer).to_string(),
+            "Error: weg (id=4)\n"
+        );
+        let e = Error::from_chained(inner::Error::from_kind(inner::ErrorKind::Gone(5)));
+        assert_eq!(
+            e.display_chain().localized(localizer).to_string(),
+            "Error: weg (id=5)\n"
+        );
+
+        let e = Error::from(ErrorKind::Auth("alice".into(), "hunter2".into()));
+        assert_eq!(
+            e.display_chain()
+                .localized(localizer)
+                .redacted()
+                .to_string(),
+            "Error: Anmeldung fehlgeschlagen (user=alice, token=[REDACTED])\n"
+        );
+    }
+}
+
+mod catalog {
+    use error_chain::catalog::Catalog;
+
+    mod inner {
+        error_chain! {
+            errors {
+                Gone {
+                    code("IN1")
+                }
+            }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        foreign_links {
+            Fmt(::std::fmt::Error);
+        }
+
+        errors {
+            /// Line one,
+            ///   "quoted" and indented.
+            #[doc = "Explicit\tattribute."]
+            Documented(n: u32) {
+                description("documented")
+                code("E1")
+            }
+            Computed(s: String) {
+                description(s)
+            }
+            #[cfg(not(unix))]
+            NotUnix {}
+            #[cfg(unix)]
+            /** Block */
+            Unix
+        }
+    }
+
+    #[test]
+    fn entries() {
+        let names: Vec<_> = ErrorKind::catalog().iter().map(|e| e.name()).collect();
+        assert_eq!(names, ErrorKind::VARIANTS);
+
+        let entry = ErrorKind::catalog()
+            .iter()
+            .find(|e| e.name() == "Documented")
+            .unwrap();
+        assert_eq!(
+            entry.doc(),
+            "Line one,\n  \"quoted\" and indented.\nExplicit\tattribute."
+        );
+        assert_eq!(entry.description(), Some("documented"));
+        assert_eq!(entry.code(), Some("E1"));
+
+        let entry = ErrorKind::catalog()
+            .iter()
+            .find(|e| e.name() == "Computed")
+            .unwrap();
+        assert_eq!(entry.doc(), "");
+        assert_eq!(entry.description(), None);
+        assert_eq!(entry.code(), None);
+
+        #[cfg(unix)]
+        assert_eq!(ErrorKind::catalog().last().unwrap().doc(), "Block");
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(ErrorKind::Documented(1).code(), Some("E1"));
+        assert_eq!(ErrorKind::Computed("a".into()).code(), None);
+        assert_eq!(ErrorKind::Inner(inner::ErrorKind::Gone).code(), Some("IN1"));
+    }
+
+    #[test]
+    fn render() {
+        let catalog = Catalog::new().register("inner", inner::ErrorKind::catalog());
+        assert_eq!(
+            catalog.to_markdown(),
+            "## inner\n\
+             \n\
+             ### `Msg`\n\
+             \n\
+             A convenient variant for String.\n\
+             \n\
+             ### `Gone`\n\
+             \n\
+             - Code: `IN1`\n"
+        );
+        assert_eq!(
+            catalog.to_json(),
+            r#"[{"name":"inner","entries":[{"name":"Msg","code":null,"description":null,"doc":"A convenient variant for String."},{"name":"Gone","code":"IN1","description":null,"doc":""}]}]"#
+        );
+        assert_eq!(Catalog::new().to_json(), "[]");
+    }
+}
+
+mod types_attributes {
+    use std::collections::HashSet;
+
+    mod inner {
+        error_chain! {
+            types {
+                Error, #[derive(PartialEq, Eq, Clone, Hash)] ErrorKind, ResultExt;
+            }
+
+            errors {
+                Timeout(secs: u32)
+            }
+        }
+    }
+
+    error_chain! {
+        types {
+            /// Errors of this module.
+            #[must_use]
+            Error,
+            #[derive(Eq, Clone, Hash)]
+            #[cfg_attr(all(), derive(PartialEq))]
+            ErrorKind,
+            #[allow(missing_docs)]
+            ResultExt,
+            #[allow(unused)]
+            Result;
+        }
+
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        errors {
+            NotFound(path: String)
+            Invalid { line: u32, column: u32 }
+        }
+    }
+
+    #[test]
+    fn derives() {
+        let kind = ErrorKind::Invalid { line: 1, column: 2 };
+        assert_eq!(kind.clone(), kind);
+        assert_ne!(kind, ErrorKind::Invalid { line: 1, column: 3 });
+        assert_eq!(ErrorKind::from("a"), ErrorKind::Msg("a".into()));
+        assert_eq!(
+            ErrorKind::Inner(inner::ErrorKind::Timeout(3)),
+            ErrorKind::Inner(inner::ErrorKind::Timeout(3))
+        );
+
+        let kinds: HashSet<_> = vec![
+            ErrorKind::NotFound("a".into()),
+            ErrorKind::NotFound("a".into()),
+            ErrorKind::NotFound("b".into()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(kinds.len(), 2);
+
+        let e = Error::from_kind(ErrorKind::NotFound("a".into()));
+        assert_eq!(*e.kind(), ErrorKind::NotFound("a".into()));
+    }
+
+    mod non_exhaustive {
+        error_chain! {
+            types {
+                Error, #[non_exhaustive] ErrorKind, ResultExt, Result;
+            }
+
+            foreign_links {
+                Fmt(::std::fmt::Error);
+            }
+
+            errors {
+                Timeout
+            }
+        }
+
+        // Matches the way a downstream crate has to, with a wildcard arm.
+        fn name(kind: &ErrorKind) -> &'static str {
+            match *kind {
+                ErrorKind::Msg(_) => "msg",
+                ErrorKind::Timeout => "timeout",
+                _ => "other",
+            }
+        }
+
+        #[test]
+        fn non_exhaustive() {
+            let e: Error = ErrorKind::Timeout.into();
+            assert_eq!(name(e.kind()), "timeout");
+            assert_eq!(name(Error::from("a").kind()), "msg");
+            assert_eq!(name(Error::from(::std::fmt::Error).kind()), "other");
+        }
+    }
+
+    mod thin {
+        error_chain! {
+            types {
+                #[must_use] Error, #[derive(Clone)] ErrorKind, ResultExt, Result;
+            }
+
+            thin
+        }
+
+        #[test]
+        fn thin() {
+            let e: Error = "message".into();
+            assert_eq!(e.kind().clone().to_string(), "message");
+        }
+    }
+}
+
+mod visibility {
+    mod inner {
+        error_chain! {
+            types {
+                #[must_use] pub(crate) Error, ErrorKind, ResultExt, Result;
+            }
+
+            errors {
+                Timeout
+            }
+        }
+
+        pub(super) mod nested {
+            error_chain! {
+                types {
+                    pub(super) Error, ErrorKind, ResultExt;
+                }
+
+                thin
+            }
+        }
+
+        pub fn nested_message() -> String {
+            let e: nested::Error = "nested".into();
+            e.to_string()
+        }
+    }
+
+    #[test]
+    fn restricted() {
+        fn fails() -> inner::Result<()> {
+            Err(inner::ErrorKind::Timeout.into())
+        }
+        use self::inner::ResultExt;
+
+        let e = fails().chain_err(|| "outer").unwrap_err();
+        assert_eq!(e.to_string(), "outer");
+        assert_eq!(inner::nested_message(), "nested");
+    }
+}
+
+mod msg_variant {
+    mod renamed {
+        error_chain! {
+            msg_variant Other;
+        }
+
+        #[test]
+        fn renamed() {
+            let e: Error = "message".into();
+            match *e.kind() {
+                ErrorKind::Other(ref s) => assert_eq!(s, "message"),
+                ref kind => panic!("{:?}", kind),
+            }
+            assert_eq!(ErrorKind::VARIANTS, &["Other"]);
+
+            fn fails() -> Result<()> {
+                bail!("failed after {} attempts", 3)
+            }
+            assert_eq!(fails().unwrap_err().to_string(), "failed after 3 attempts");
+        }
+    }
+
+    mod payload {
+        use std::borrow::Cow;
+
+        error_chain! {
+            msg_variant Message(Cow<'static, str>);
+        }
+
+        #[test]
+        fn payload() {
+            let kind = ErrorKind::Message(Cow::Borrowed("static"));
+            assert_eq!(kind.to_string(), "static");
+            assert_eq!(kind.description(), "static");
+
+            let e: Error = String::from("owned").into();
+            match *e.kind() {
+                ErrorKind::Message(Cow::Owned(ref s)) => assert_eq!(s, "owned"),
+                ref kind => panic!("{:?}", kind),
+            }
+            let e: Error = "borrowed".into();
+            assert_eq!(e.to_string(), "borrowed");
+        }
+    }
+
+    mod without_from {
+        use std::io;
+
+        error_chain! {
+            foreign_links {
+                Io(io::Error);
+            }
+
+            msg_variant Msg without_from;
+        }
+
+        // Would be ambiguous with the conversions from strings.
+        impl<'a> From<&'a str> for ErrorKind {
+            fn from(s: &'a str) -> Self {
+                ErrorKind::Msg(format!("note: {}", s))
+            }
+        }
+
+        #[test]
+        fn without_from() {
+            let e = Error::from_kind(ErrorKind::Msg("message".into()));
+            assert_eq!(e.to_string(), "message");
+            let r: Result<()> = Err(io::Error::from(io::ErrorKind::NotFound)).chain_err(|| "read");
+            assert_eq!(r.unwrap_err().to_string(), "note: read");
+        }
+    }
+}
+
+mod foreign_link_map {
+    use std::fmt;
+    use std::io;
+
+    error_chain! {
+        foreign_links {
+            Fmt(fmt::Error) => |_| ErrorKind::Format;
+            Io(io::Error) #[cfg(unix)] => map_io;
+        }
+
+        errors {
+            NotFound(path: String)
+            Format
+        }
+    }
+
+    #[cfg(unix)]
+    fn map_io(e: io::Error) -> ErrorKind {
+        match e.kind() {
+            io::ErrorKind::NotFound => ErrorKind::NotFound(e.to_string()),
+            _ => ErrorKind::Io(e),
+        }
+    }
+
+    #[test]
+    fn closure() {
+        let e = Error::from(fmt::Error);
+        assert_kind!(e, ErrorKind::Format);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn function() {
+        fn open() -> Result<()> {
+            Err(io::Error::new(io::ErrorKind::NotFound, "a.txt"))?;
+            Ok(())
+        }
+        match *open().unwrap_err().kind() {
+            ErrorKind::NotFound(ref path) => assert_eq!(path, "a.txt"),
+            ref kind => panic!("{:?}", kind),
+        }
+
+        let e = Error::from(io::Error::from(io::ErrorKind::PermissionDenied));
+        match *e.kind() {
+            ErrorKind::Io(ref e) => assert_eq!(e.kind(), io::ErrorKind::PermissionDenied),
+            ref kind => panic!("{:?}", kind),
+        }
+    }
+}
+
+mod boxed {
+    use std::error::Error as StdError;
+    use std::fmt;
+    use std::mem::size_of;
+
+    #[derive(Debug)]
+    pub struct ParseError {
+        spans: [u64; 16],
+        cause: fmt::Error,
+    }
+
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "parse error at {}", self.spans[0])
+        }
+    }
+
+    impl StdError for ParseError {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            Some(&self.cause)
+        }
+    }
+
+    error_chain! {
+        foreign_links {
+            #[boxed]
+            Parse(ParseError);
+        }
+
+        errors {
+            #[boxed(SpansDetails)]
+            /// A large struct variant.
+            Spans { spans: [u64; 16], line: u32, } {
+                display("invalid spans at line {}", line)
+                retryable(*line < 3)
+            }
+            #[boxed(TupleDetails)]
+            Tuple(spans: [u64; 16]) {
+                description("tuple")
+                display("{} spans", spans.len())
+            }
+        }
+    }
+
+    fn parse_error() -> ParseError {
+        ParseError {
+            spans: [7; 16],
+            cause: fmt::Error,
+        }
+    }
+
+    #[test]
+    fn size() {
+        assert!(size_of::<ErrorKind>() < size_of::<ParseError>());
+    }
+
+    #[test]
+    fn foreign_link() {
+        fn parse() -> Result<()> {
+            Err(parse_error())?;
+            Ok(())
+        }
+
+        let e = parse().unwrap_err();
+        assert_eq!(e.to_string(), "parse error at 7");
+        match *e.kind() {
+            ErrorKind::Parse(ref p) => assert_eq!(p.spans[0], 7),
+            ref kind => panic!("{:?}", kind),
+        }
+        assert!(e.source().unwrap().is::<fmt::Error>());
+        assert_chain_contains!(e, ParseError);
+    }
+
+    #[test]
+    fn errors() {
+        let kind = ErrorKind::from(SpansDetails {
+            spans: [0; 16],
+            line: 3,
+        });
+        assert_eq!(kind.to_string(), "invalid spans at line 3");
+        assert!(!kind.is_retryable());
+        assert!(format!("{:?}", kind).starts_with("Spans { spans: [0,"));
+        match kind {
+            ErrorKind::Spans(ref details) => assert_eq!(details.line, 3),
+            ref kind => panic!("{:?}", kind),
+        }
+
+        let kind: ErrorKind = SpansDetails {
+            spans: [0; 16],
+            line: 1,
+        }
+        .into();
+        assert!(kind.is_retryable());
+
+        let kind = ErrorKind::Tuple(Box::new(TupleDetails { spans: [0; 16] }));
+        assert_eq!(kind.to_string(), "16 spans");
+        assert_eq!(kind.description(), "tuple");
+    }
+
+    // The traits derived on the kind are derived on the boxed structs too.
+    mod derives {
+        error_chain! {
+            types {
+                Error, #[derive(PartialEq, Clone)] #[derive(Eq,)] ErrorKind, ResultExt, Result;
+            }
+
+            errors {
+                #[boxed(Location)]
+                At { file: String, line: u32 } {
+                    display("{}:{}", file, line)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn derives() {
+        use self::derives::{ErrorKind, Location};
+
+        let kind = ErrorKind::from(Location {
+            file: "a.rs".into(),
+            line: 1,
+        });
+        assert_eq!(kind.clone(), kind);
+        assert_ne!(kind, ErrorKind::Msg("a.rs:1".into()));
+        let location = Location {
+            file: "a.rs".into(),
+            line: 2,
+        };
+        assert_eq!(location.clone(), location);
+    }
+}
+
+#[cfg(feature = "std")]
+mod classify {
+    use error_chain::classify::Severity;
+    use error_chain::ChainedError;
+    use std::io;
+
+    mod inner {
+        error_chain! {
+            errors {
+                Busy {
+                    display("busy")
+                    retryable
+                    severity(Warning)
+                }
+            }
+        }
+    }
+
+    mod upstream {
+        error_chain! {
+            errors {
+                Crashed {
+                    display("crashed")
+                    severity(Fatal)
+                }
+            }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        foreign_links {
+            Io(io::Error);
+        }
+
+        opaque_links {
+            Upstream;
+        }
+
+        errors {
+            Status(code: u16) {
+                display("status {}", code)
+                retryable(*code >= 500)
+            }
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        let kind = ErrorKind::Msg("failed".into());
+        assert_eq!(kind.severity(), Severity::Error);
+        assert!(!kind.is_retryable());
+    }
+
+    #[test]
+    fn clauses() {
+        assert!(ErrorKind::Status(503).is_retryable());
+        assert!(!ErrorKind::Status(404).is_retryable());
+        assert_eq!(ErrorKind::Status(503).severity(), Severity::Error);
+        assert!(inner::ErrorKind::Busy.is_retryable());
+        assert_eq!(inner::ErrorKind::Busy.severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn linked() {
+        let e = Error::from(inner::Error::from(inner::ErrorKind::Busy));
+        assert!(e.kind().is_retryable());
+        assert_eq!(e.kind().severity(), Severity::Warning);
+
+        let e = Error::from_chained(upstream::Error::from(upstream::ErrorKind::Crashed));
+        assert!(!e.kind().is_retryable());
+        assert_eq!(e.kind().severity(), Severity::Fatal);
+    }
+
+    mod classified {
+        use std::{fmt, io};
+
+        fn not_found_is_retryable(e: &io::Error) -> bool {
+            e.kind() == io::ErrorKind::NotFound
+        }
+
+        error_chain! {
+            foreign_links {
+                Io(io::Error) retryable(not_found_is_retryable);
+                #[boxed]
+                Fmt(fmt::Error) retryable(|_: &fmt::Error| true);
+            }
+        }
+    }
+
+    #[test]
+    fn foreign() {
+        let e = Error::from(io::Error::from(io::ErrorKind::TimedOut));
+        assert!(e.kind().is_retryable());
+        let e = Error::from(io::Error::from(io::ErrorKind::NotFound));
+        assert!(!e.kind().is_retryable());
+    }
+
+    #[test]
+    fn foreign_classifier() {
+        let e = classified::Error::from(io::Error::from(io::ErrorKind::NotFound));
+        assert!(e.kind().is_retryable());
+        assert!(e.any_retryable());
+        let e = classified::Error::from(io::Error::from(io::ErrorKind::TimedOut));
+        assert!(!e.kind().is_retryable());
+        assert!(!e.any_retryable());
+        let e = classified::Error::from(::std::fmt::Error);
+        assert!(e.kind().is_retryable());
+    }
+
+    #[test]
+    fn chain() {
+        let e = Error::from(ErrorKind::Status(503)).chain_err(|| "cannot fetch");
+        assert!(!e.kind().is_retryable());
+        assert!(e.any_retryable());
+
+        let e = Error::from(inner::Error::from(inner::ErrorKind::Busy).chain_err(|| "locked"))
+            .chain_err(|| "cannot write");
+        assert!(e.any_retryable());
+
+        let e: Error = Err::<(), _>(io::Error::from(io::ErrorKind::Interrupted))
+            .chain_err(|| "cannot read")
+            .unwrap_err();
+        assert!(e.any_retryable());
+
+        let e = Error::from(ErrorKind::Status(404)).chain_err(|| "cannot fetch");
+        assert!(!e.any_retryable());
+    }
+
+    // Not listed in `links`.
+    mod unlinked {
+        error_chain! {
+            errors {
+                Throttled {
+                    display("throttled")
+                    retryable
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unlinked_cause() {
+        let e: Error = Err::<(), _>(unlinked::Error::from(unlinked::ErrorKind::Throttled))
+            .chain_err(|| "cannot fetch")
+            .unwrap_err();
+        assert!(e.any_retryable());
+
+        let e = Error::with_chain(
+            unlinked::Error::from(unlinked::ErrorKind::Throttled).chain_err(|| "slow down"),
+            "cannot fetch",
+        );
+        assert!(e.any_retryable());
+
+        let e = Error::with_chain(unlinked::Error::from("denied"), "cannot fetch");
+        assert!(!e.any_retryable());
+    }
+}
+
+#[cfg(feature = "std")]
+mod retry {
+    use error_chain::retry::{retry_with, Attempts, Clock, Policy, Sleeper};
+    use error_chain::ChainedError;
+    use std::cell::{Cell, RefCell};
+    use std::time::{Duration, Instant};
+
+    // Without a `Msg` variant, the kind of the final error is built by the
+    // caller.
+    error_chain! {
+        skip_msg_variant
+
+        errors {
+            Busy(n: u32) {
+                display("busy {}", n)
+                retryable
+            }
+            Denied {
+                display("denied")
+            }
+            GaveUp(attempts: usize) {
+                display("gave up after {} attempts", attempts)
+            }
+        }
+    }
+
+    // Not listed in `links`.
+    mod unlinked {
+        error_chain! {
+            errors {
+                Throttled {
+                    display("throttled")
+                    retryable
+                }
+            }
+        }
+    }
+
+    fn gave_up(attempts: &Attempts<Error>) -> ErrorKind {
+        ErrorKind::GaveUp(attempts.len())
+    }
+
+    struct FakeTime {
+        start: Instant,
+        offset: Cell<Duration>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeTime {
+        fn new() -> FakeTime {
+            FakeTime {
+                start: Instant::now(),
+                offset: Cell::new(Duration::from_secs(0)),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Clock for FakeTime {
+        fn now(&self) -> Instant {
+            self.start + self.offset.get()
+        }
+    }
+
+    impl Sleeper for FakeTime {
+        fn sleep(&self, duration: Duration) {
+            self.offset.set(self.offset.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn succeeds_after_retries() {
+        let time = FakeTime::new();
+        let mut runs = 0;
+        let op = || {
+            runs += 1;
+            if runs < 3 {
+                Err(ErrorKind::Busy(runs).into())
+            } else {
+                Ok(runs)
+            }
+        };
+        let res: Result<u32> = retry_with(&Policy::new(), &time, &time, op, gave_up);
+        assert_eq!(res.unwrap(), 3);
+        assert_eq!(
+            *time.sleeps.borrow(),
+            [Duration::from_millis(100), Duration::from_millis(200)]
+        );
+    }
+
+    #[test]
+    fn records_every_attempt() {
+        let time = FakeTime::new();
+        let mut runs = 0;
+        let policy = Policy::new()
+            .max_attempts(5)
+            .initial_delay(Duration::from_secs(1))
+            .multiplier(3)
+            .max_delay(Duration::from_secs(5));
+        let op = || -> Result<()> {
+            runs += 1;
+            Err(Error::from(ErrorKind::Busy(runs)).chain_err(|| ErrorKind::Denied))
+        };
+        let res = retry_with(&policy, &time, &time, op, gave_up);
+        assert_eq!(
+            *time.sleeps.borrow(),
+            [1, 3, 5, 5]
+                .iter()
+                .map(|&s| Duration::from_secs(s))
+                .collect::<Vec<_>>()
+        );
+
+        let e = res.unwrap_err();
+        assert_eq!(e.to_string(), "gave up after 5 attempts");
+        assert!(!e.any_retryable());
+        assert_eq!(
+            e.iter().skip(1).map(|e| e.to_string()).collect::<Vec<_>>(),
+            ["attempt 1 after 0ns: denied; attempt 2 after 1s: denied; \
+                 attempt 3 after 4s: denied; attempt 4 after 9s: denied; \
+                 attempt 5 after 14s: denied"]
+        );
+
+        let attempts = e
+            .iter()
+            .nth(1)
+            .and_then(|e| e.downcast_ref::<Attempts<Error>>())
+            .unwrap();
+        let last = attempts.last().unwrap();
+        assert_eq!(last.number(), 5);
+        assert_eq!(last.elapsed(), Duration::from_secs(14));
+        assert!(last.error().any_retryable());
+    }
+
+    #[test]
+    fn retries_unlinked_cause() {
+        let time = FakeTime::new();
+        let mut runs = 0;
+        let op = || -> Result<u32> {
+            runs += 1;
+            if runs < 2 {
+                Err(unlinked::Error::from(unlinked::ErrorKind::Throttled))
+                    .chain_err(|| ErrorKind::Denied)
+            } else {
+                Ok(runs)
+            }
+        };
+        let res = retry_with(&Policy::new(), &time, &time, op, gave_up);
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    #[test]
+    fn returns_non_retryable_unchanged() {
+        let time = FakeTime::new();
+        let mut runs = 0;
+        let op = || -> Result<()> {
+            runs += 1;
+            Err(ErrorKind::Denied.into())
+        };
+        let res = retry_with(&Policy::new(), &time, &time, op, gave_up);
+        assert_eq!(runs, 1);
+        assert!(time.sleeps.borrow().is_empty());
+        assert_kind!(res.unwrap_err(), ErrorKind::Denied);
+    }
+
+    #[test]
+    fn stops_on_non_retryable() {
+        let time = FakeTime::new();
+        let mut runs = 0;
+        let op = || -> Result<()> {
+            runs += 1;
+            if runs < 2 {
+                Err(ErrorKind::Busy(runs).into())
+            } else {
+                Err(ErrorKind::Denied.into())
+            }
+        };
+        let res = retry_with(&Policy::new(), &time, &time, op, gave_up);
+        assert_eq!(runs, 2);
+        let e = res.unwrap_err();
+        assert_eq!(e.to_string(), "gave up after 2 attempts");
+        assert_eq!(
+            e.iter().nth(1).unwrap().to_string(),
+            "attempt 1 after 0ns: busy 1; attempt 2 after 100ms: denied"
+        );
+    }
+
+    #[test]
+    fn nested() {
+        let time = FakeTime::new();
+        let mut runs = 0;
+        let policy = Policy::new().max_attempts(2);
+        let res: Result<()> = retry_with(
+            &policy,
+            &time,
+            &time,
+            || {
+                retry_with(
+                    &policy,
+                    &time,
+                    &time,
+                    || {
+                        runs += 1;
+                        Err(ErrorKind::Busy(runs).into())
+                    },
+                    gave_up,
+                )
+            },
+            gave_up,
+        );
+        assert_eq!(runs, 2);
+        assert_kind!(res.unwrap_err(), ErrorKind::GaveUp(2));
+    }
+
+    #[test]
+    fn system_clock() {
+        let policy = Policy::new().initial_delay(Duration::from_millis(1));
+        let mut runs = 0;
+        let op = || -> Result<()> {
+            runs += 1;
+            Err(ErrorKind::Busy(runs).into())
+        };
+        let res = error_chain::retry(&policy, op, gave_up);
+        assert_kind!(res.unwrap_err(), ErrorKind::GaveUp(3));
+    }
+}
+
+mod help {
+    use error_chain::ChainedError;
+
+    mod inner {
+        error_chain! {
+            errors {
+                Locked(path: String) {
+                    display("{} is locked", path)
+                    help("remove {}.lock", path)
+                }
+            }
+        }
+    }
+
+    mod upstream {
+        error_chain! {
+            errors {
+                Offline {
+                    display("offline")
+                    help("check your connection")
+                }
+            }
+        }
+    }
+
+    // Not listed in `links`.
+    mod unlinked {
+        error_chain! {
+            errors {
+                Throttled {
+                    display("throttled")
+                    help("wait")
+                }
+            }
+        }
+    }
+
+    error_chain! {
+        links {
+            Inner(inner::Error, inner::ErrorKind);
+        }
+
+        opaque_links {
+            Upstream;
+        }
+
+        errors {
+            Login(user: String, token: String) {
+                display("cannot log {} in", user)
+                help("token {} expired for {}", token, user)
+                sensitive(token)
+            }
+        }
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(
+            inner::ErrorKind::Locked("db".into()).help(),
+            Some("remove db.lock".into())
+        );
+        assert_eq!(ErrorKind::Msg("failed".into()).help(), None);
+    }
+
+    #[test]
+    fn linked() {
+        let e = Error::from(inner::Error::from(inner::ErrorKind::Locked("db".into())));
+        assert_eq!(e.help(), ["remove db.lock"]);
+        let e = Error::from_chained(upstream::Error::from(upstream::ErrorKind::Offline));
+        assert_eq!(e.help(), ["check your connection"]);
+    }
+
+    #[test]
+    fn with_help() {
+        let e = Error::from(inner::Error::from(inner::ErrorKind::Locked("db".into())))
+            .with_help("retry later")
+            .with_help("or ask an admin");
+        assert_eq!(
+            e.help(),
+            ["remove db.lock", "retry later", "or ask an admin"]
+        );
+        let e = e.chain_err(|| "cannot write");
+        assert!(e.help().is_empty());
+    }
+
+    #[test]
+    fn display_chain() {
+        let e = inner::Error::from(inner::ErrorKind::Locked("db".into()))
+            .chain_err(|| "cannot open")
+            .with_help("is another process running?");
+        let e = Error::with_chain(e, "cannot start");
+        assert_eq!(
+            e.display_chain().normalized().to_string(),
+            "Error: cannot start\n\
+             Caused by: cannot open\n\
+             Caused by: db is locked\n\
+             help: is another process running?\n\
+             help: remove db.lock\n"
+        );
+    }
+
+    #[test]
+    fn unlinked_cause() {
+        let e = unlinked::Error::from(unlinked::ErrorKind::Throttled).with_help("or retry later");
+        let e: Error = Err::<(), _>(e).chain_err(|| "cannot fetch").unwrap_err();
+        assert_eq!(
+            e.display_chain().normalized().to_string(),
+            "Error: cannot fetch\n\
+             Caused by: throttled\n\
+             help: wait\n\
+             help: or retry later\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn redacted() {
+        let e = Error::from(ErrorKind::Login("alice".into(), "abc".into()));
+        assert_eq!(
+            e.display_chain().redacted().normalized().to_string(),
+            "Error: cannot log alice in\nhelp: token [REDACTED] expired for alice\n"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+// Checks the messages of the `compile_error!` arms reporting malformed
+// `error_chain!` invocations. After changing a message, run with
+// `TRYBUILD=overwrite` to update the `.stderr` files.
+
+extern crate trybuild;
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,12 @@
+// The expansion checks cfgs set by the build script of error-chain.
+#![allow(unknown_lints, unexpected_cfgs)]
+
+#[macro_use]
+extern crate error_chain;
+
+error_chain! {
+    skip_msg_variant
+    msg_variant Other;
+}
+
+fn main() {}
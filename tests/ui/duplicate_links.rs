@@ -0,0 +1,25 @@
+// The expansion checks cfgs set by the build script of error-chain.
+#![allow(unknown_lints, unexpected_cfgs)]
+
+#[macro_use]
+extern crate error_chain;
+
+mod inner {
+    error_chain! {}
+}
+
+error_chain! {
+    links {
+        Inner(inner::Error, inner::ErrorKind);
+    }
+
+    errors {
+        Foo
+    }
+
+    links {
+        Other(inner::Error, inner::ErrorKind);
+    }
+}
+
+fn main() {}
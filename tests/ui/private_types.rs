@@ -0,0 +1,17 @@
+// The expansion checks cfgs set by the build script of error-chain.
+#![allow(unknown_lints, unexpected_cfgs)]
+
+#[macro_use]
+extern crate error_chain;
+
+mod inner {
+    error_chain! {
+        types {
+            pub(self) Error, ErrorKind, ResultExt, Result;
+        }
+    }
+}
+
+fn main() {
+    let _ = inner::ErrorKind::Msg("private".into());
+}